@@ -0,0 +1,160 @@
+//! Drawing a day's workings a frame at a time, either redrawn in place in the
+//! terminal or written out one after another as plain text.
+
+use crate::colour::Colour;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+/// A day's animation: parses `input`, then passes each frame to `show` as the
+/// solution works through it.
+pub type Animation = fn(input: &str, show: &mut dyn FnMut(Frame)) -> Result<(), ParseError>;
+
+/// What's drawn in one place in a frame.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Colour,
+}
+
+impl Cell {
+    pub fn new(glyph: char, colour: Colour) -> Self {
+        Cell { glyph, colour }
+    }
+}
+
+/// One picture in an animation, with a line saying what it shows.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    pub caption: String,
+    pub cells: Grid<Cell>,
+}
+
+impl Frame {
+    pub fn new(caption: impl Into<String>, cells: Grid<Cell>) -> Self {
+        Frame {
+            caption: caption.into(),
+            cells,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Style {
+    /// Coloured, each frame drawn over the last after a pause.
+    Terminal,
+    /// Uncoloured, each frame after the last with no pause.
+    Plain,
+}
+
+/// Shows frames as they're made.
+pub struct Player<W> {
+    out: W,
+    style: Style,
+    delay: Duration,
+    frames_shown: usize,
+}
+
+impl<W: Write> Player<W> {
+    /// Redraws each frame in place, in colour, `fps` times a second.
+    pub fn terminal(out: W, fps: u32) -> Self {
+        Player {
+            out,
+            style: Style::Terminal,
+            delay: Duration::from_secs(1) / fps.max(1),
+            frames_shown: 0,
+        }
+    }
+
+    /// Writes the frames one after another as plain text, for a file or a pipe.
+    pub fn plain(out: W) -> Self {
+        Player {
+            out,
+            style: Style::Plain,
+            delay: Duration::ZERO,
+            frames_shown: 0,
+        }
+    }
+
+    pub fn frames_shown(&self) -> usize {
+        self.frames_shown
+    }
+
+    pub fn show(&mut self, frame: &Frame) -> io::Result<()> {
+        match self.style {
+            Style::Terminal => {
+                if self.frames_shown == 0 {
+                    // Clear the screen once, then draw over it from the top.
+                    write!(self.out, "\x1b[2J")?;
+                } else {
+                    thread::sleep(self.delay);
+                }
+                writeln!(self.out, "\x1b[H{}\x1b[K", frame.caption)?;
+                for row in frame.cells.rows() {
+                    let mut colour = None;
+                    for cell in row {
+                        if colour != Some(cell.colour) {
+                            let Colour { r, g, b } = cell.colour;
+                            write!(self.out, "\x1b[38;2;{};{};{}m", r, g, b)?;
+                            colour = Some(cell.colour);
+                        }
+                        write!(self.out, "{}", cell.glyph)?;
+                    }
+                    writeln!(self.out, "\x1b[0m\x1b[K")?;
+                }
+                // Clear whatever's left below of a larger frame.
+                write!(self.out, "\x1b[J")?;
+            }
+            Style::Plain => {
+                writeln!(self.out, "{}", frame.caption)?;
+                for row in frame.cells.rows() {
+                    writeln!(
+                        self.out,
+                        "{}",
+                        row.iter().map(|cell| cell.glyph).collect::<String>()
+                    )?;
+                }
+                writeln!(self.out)?;
+            }
+        }
+        self.frames_shown += 1;
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+fn checkerboard() -> Frame {
+    Frame::new(
+        "step 1",
+        Grid::from_fn(2, 2, |pos| {
+            if (pos.x + pos.y) % 2 == 0 {
+                Cell::new('#', Colour::WHITE)
+            } else {
+                Cell::new('.', Colour::BLACK)
+            }
+        }),
+    )
+}
+
+#[test]
+fn plain_frames() {
+    let mut player = Player::plain(vec![]);
+    player.show(&checkerboard()).unwrap();
+    player.show(&checkerboard()).unwrap();
+    assert_eq!(2, player.frames_shown());
+    assert_eq!(
+        "step 1\n#.\n.#\n\nstep 1\n#.\n.#\n\n",
+        String::from_utf8(player.out).unwrap()
+    );
+}
+
+#[test]
+fn terminal_frames() {
+    let mut player = Player::terminal(vec![], 1000);
+    player.show(&checkerboard()).unwrap();
+    let drawn = String::from_utf8(player.out).unwrap();
+    assert!(drawn.starts_with("\x1b[2J\x1b[Hstep 1\x1b[K\n"));
+    assert!(drawn.contains("\x1b[38;2;255;255;255m#\x1b[38;2;0;0;0m.\x1b[0m"));
+}
@@ -0,0 +1,62 @@
+//! The answers each day's real input is known to give, kept in an `answers.toml`
+//! next to its `input.txt` so a refactor can be checked against them. Either of
+//! its `part1` and `part2` keys may be missing.
+
+use crate::days::Part;
+use crate::error::ParseError;
+use crate::toml;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Answers {
+    pub fn new(part1: Option<String>, part2: Option<String>) -> Self {
+        Answers { part1, part2 }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut answers = Answers::default();
+        for entry in toml::entries(text)? {
+            match entry.key {
+                "part1" => answers.part1 = Some(entry.value),
+                "part2" => answers.part2 = Some(entry.value),
+                _ => return Err(entry.unexpected_key("`part1` or `part2`")),
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+#[test]
+fn parses_every_kind_of_value() {
+    let answers = Answers::parse("# day13\npart1 = 675\npart2 = '''\n#..#\n####\n'''\n").unwrap();
+    assert_eq!(Some("675"), answers.get(Part::One));
+    assert_eq!(Some("#..#\n####\n"), answers.get(Part::Two));
+
+    let answers = Answers::parse("part2 = \"a \\\"b\\\"\"").unwrap();
+    assert_eq!(None, answers.get(Part::One));
+    assert_eq!(Some("a \"b\""), answers.get(Part::Two));
+}
+
+#[test]
+fn rejects_malformed_files() {
+    for (text, line) in [
+        ("part1 = 1\npart3 = 2", 2),
+        ("part1 = 1\npart1 = 2", 2),
+        ("part1 = \"12", 1),
+        ("part1 = '''\n12\n", 2),
+        ("part1 = twelve", 1),
+    ] {
+        assert_eq!(line, Answers::parse(text).unwrap_err().line, "{:?}", text);
+    }
+}
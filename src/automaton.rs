@@ -0,0 +1,127 @@
+//! Running a state forward a step at a time, as in the puzzles where some rule
+//! is applied over and over to a grid or a set of counts.
+
+use crate::work;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Applies `step` to `state` `steps` times.
+pub fn run<S>(state: &mut S, steps: usize, mut step: impl FnMut(&mut S)) {
+    for _ in 0..steps {
+        work::step();
+        step(state);
+    }
+}
+
+/// Applies `step` to `state` until `done` holds after a step, and returns how
+/// many steps that took. Never returns if `done` never holds.
+pub fn run_until<S>(
+    state: &mut S,
+    mut step: impl FnMut(&mut S),
+    mut done: impl FnMut(&S) -> bool,
+) -> usize {
+    let mut steps = 0;
+    loop {
+        work::step();
+        step(state);
+        steps += 1;
+        if done(state) {
+            return steps;
+        }
+    }
+}
+
+/// A run of states that ends up going round in circles.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cycle<S> {
+    /// The first step of the loop.
+    pub start: usize,
+    /// Every state from the start up to the last before the loop comes round,
+    /// indexed by the number of steps taken to reach it.
+    pub states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    pub fn length(&self) -> usize {
+        self.states.len() - self.start
+    }
+
+    /// The state after any number of steps, however far past the loop that is.
+    pub fn state_at(&self, step: usize) -> &S {
+        if step < self.start {
+            &self.states[step]
+        } else {
+            &self.states[self.start + (step - self.start) % self.length()]
+        }
+    }
+}
+
+/// Steps `state` until it comes back to one it's been in before. Every state
+/// along the way is kept, so this is only for automata that do repeat.
+pub fn find_cycle<S: Clone + Eq + Hash>(state: S, step: impl FnMut(&mut S)) -> Cycle<S> {
+    match find_cycle_within(state, usize::MAX, step) {
+        Ok(cycle) => cycle,
+        Err(_) => unreachable!("ran out of steps"),
+    }
+}
+
+/// The state after `steps` steps, which may be far more than could be run one at
+/// a time (10^12, say), provided the automaton repeats itself well before then.
+pub fn fast_forward<S: Clone + Eq + Hash>(state: S, steps: usize, step: impl FnMut(&mut S)) -> S {
+    match find_cycle_within(state, steps, step) {
+        Ok(cycle) => cycle.state_at(steps).clone(),
+        Err(state) => state,
+    }
+}
+
+/// Looks for a cycle in the first `limit` steps, giving up with the state
+/// reached after them if there isn't one.
+fn find_cycle_within<S: Clone + Eq + Hash>(
+    mut state: S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+) -> Result<Cycle<S>, S> {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    for taken in 0..limit {
+        if let Some(&start) = seen.get(&state) {
+            return Ok(Cycle { start, states });
+        }
+        seen.insert(state.clone(), taken);
+        states.push(state.clone());
+        work::step();
+        step(&mut state);
+    }
+    Err(state)
+}
+
+#[test]
+fn runs_until_done() {
+    let mut state = 1;
+    assert_eq!(4, run_until(&mut state, |n| *n *= 3, |&n| n > 50));
+    assert_eq!(81, state);
+}
+
+#[test]
+fn fast_forwards_through_a_cycle() {
+    // 3, 10, 5, 16, 8, 4, 2, 1, 4, 2, 1, ...
+    let collatz = |n: &mut u64| {
+        *n = if n.is_multiple_of(2) {
+            *n / 2
+        } else {
+            *n * 3 + 1
+        }
+    };
+
+    let cycle = find_cycle(3, collatz);
+    assert_eq!((5, 3), (cycle.start, cycle.length()));
+    assert_eq!(&16, cycle.state_at(3));
+
+    for steps in [0, 4, 7, 8, 20] {
+        let mut state = 3;
+        run(&mut state, steps, collatz);
+        assert_eq!(state, fast_forward(3, steps, collatz));
+    }
+    // (10^12 - 5) % 3 == 2, two steps round the loop from 4.
+    assert_eq!(1, fast_forward(3, 1_000_000_000_000, collatz));
+}
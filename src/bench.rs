@@ -0,0 +1,168 @@
+//! Repeatedly timing each phase of a day, and comparing against a saved baseline.
+
+use crate::days::{Day, Part};
+use crate::error::ParseError;
+use crate::solution::timed;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// A separately timed step of solving a day.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [
+        Phase::Parse,
+        Phase::Solve(Part::One),
+        Phase::Solve(Part::Two),
+    ];
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => f.write_str("parse"),
+            Phase::Solve(part) => part.fmt(f),
+        }
+    }
+}
+
+/// Summary statistics over a set of timings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Panics if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+        let mean = sorted.iter().sum::<Duration>() / count as u32;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Times each phase of `day` on `input` `runs` times, after `warmup` untimed runs.
+pub fn bench_day(
+    day: &Day,
+    input: &str,
+    runs: usize,
+    warmup: usize,
+) -> Result<Vec<Measurement>, ParseError> {
+    let parsed = day
+        .solution
+        .parse_any(input)
+        .map_err(|e| e.in_day(day.number))?;
+
+    Ok(Phase::ALL
+        .iter()
+        .map(|&phase| {
+            let run_once = || match phase {
+                Phase::Parse => {
+                    // Dropped once timed, so that freeing it doesn't count.
+                    let reparsed = timed(|| day.solution.parse_any(input));
+                    reparsed.elapsed
+                }
+                Phase::Solve(part) => {
+                    timed(|| day.solution.solve_any(parsed.as_ref(), part)).elapsed
+                }
+            };
+            for _ in 0..warmup {
+                run_once();
+            }
+            let samples = (0..runs).map(|_| run_once()).collect::<Vec<_>>();
+            Measurement {
+                day: day.number,
+                phase,
+                stats: Stats::from_samples(&samples),
+            }
+        })
+        .collect())
+}
+
+/// The median time of each day's phases from an earlier run.
+pub type Baseline = HashMap<(u8, Phase), Duration>;
+
+/// Saves the medians as `dayN phase nanoseconds` lines.
+pub fn save_baseline(path: &Path, measurements: &[Measurement]) -> io::Result<()> {
+    let lines = measurements
+        .iter()
+        .map(|m| format!("day{} {} {}\n", m.day, m.phase, m.stats.median.as_nanos()))
+        .collect::<String>();
+    fs::write(path, lines)
+}
+
+pub fn load_baseline(path: &Path) -> io::Result<Baseline> {
+    let invalid = |line: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unexpected baseline line `{}`", line),
+        )
+    };
+    fs::read_to_string(path)?
+        .lines()
+        .map(|line| {
+            let mut fields = line.split_whitespace();
+            let day = fields
+                .next()
+                .and_then(|day| day.strip_prefix("day")?.parse().ok());
+            let phase = fields.next().and_then(|name| {
+                Phase::ALL
+                    .into_iter()
+                    .find(|phase| phase.to_string() == name)
+            });
+            let nanos = fields.next().and_then(|nanos| nanos.parse().ok());
+            match (day, phase, nanos, fields.next()) {
+                (Some(day), Some(phase), Some(nanos), None) => {
+                    Ok(((day, phase), Duration::from_nanos(nanos)))
+                }
+                _ => Err(invalid(line)),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn stats() {
+    let samples = [4, 1, 3, 2].map(Duration::from_millis);
+    let stats = Stats::from_samples(&samples);
+
+    assert_eq!(Duration::from_millis(1), stats.min);
+    assert_eq!(Duration::from_micros(2500), stats.median);
+    assert_eq!(Duration::from_micros(2500), stats.mean);
+    assert_eq!(1118, stats.stddev.as_micros());
+}
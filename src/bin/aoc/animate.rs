@@ -0,0 +1,53 @@
+//! `aoc run --animate`, which plays a day's animation before printing its answers.
+
+use advent_of_code_2021::animate::Player;
+use advent_of_code_2021::days::Day;
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::Path;
+
+/// Plays the day's animation of `input` in the terminal at `fps` frames a second,
+/// or writes the frames to `frames_file` as plain text if one is given. Frames go
+/// to standard output as plain text too when it isn't a terminal. Returns whether
+/// the whole animation was shown.
+pub fn animate(day: &Day, input: &str, fps: u32, frames_file: Option<&Path>) -> bool {
+    let Some(animation) = day.animation else {
+        eprintln!("day{} has no animation", day.number);
+        return false;
+    };
+    let mut player: Player<Box<dyn Write>> = match frames_file {
+        Some(path) => match File::create(path) {
+            Ok(file) => Player::plain(Box::new(BufWriter::new(file))),
+            Err(e) => {
+                eprintln!("couldn't create {}: {}", path.display(), e);
+                return false;
+            }
+        },
+        None if io::stdout().is_terminal() => Player::terminal(Box::new(io::stdout()), fps),
+        None => Player::plain(Box::new(io::stdout())),
+    };
+
+    // Once a frame fails to show there's no point trying the rest.
+    let mut error = None;
+    let result = animation(input, &mut |frame| {
+        if error.is_none() {
+            error = player.show(&frame).err();
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("{}", e.in_day(day.number));
+        return false;
+    }
+    if let Some(e) = error {
+        eprintln!("couldn't show day{}'s animation: {}", day.number, e);
+        return false;
+    }
+    if let Some(path) = frames_file {
+        println!(
+            "wrote {} frames to {}",
+            player.frames_shown(),
+            path.display()
+        );
+    }
+    true
+}
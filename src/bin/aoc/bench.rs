@@ -0,0 +1,121 @@
+//! `aoc bench`: timing each phase of a day over repeated runs.
+
+use crate::table::print_table;
+use advent_of_code_2021::bench::{self, Baseline, Measurement};
+use advent_of_code_2021::days::Day;
+use std::borrow::Cow;
+use std::path::Path;
+
+pub struct Options<'a> {
+    pub runs: usize,
+    pub warmup: usize,
+    pub save_baseline: Option<&'a Path>,
+    pub baseline: Option<&'a Path>,
+    /// How much slower than the baseline, in percent, counts as a regression.
+    pub threshold: f64,
+}
+
+/// Benchmarks each day on its input and prints the statistics, compared with the
+/// baseline if there is one. Returns whether everything ran without regressing.
+pub fn bench(inputs: &[(&Day, Cow<str>)], options: &Options) -> bool {
+    let baseline = match options.baseline.map(bench::load_baseline).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("couldn't read the baseline: {}", e);
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    let mut measurements = vec![];
+    for (day, input) in inputs {
+        match bench::bench_day(day, input, options.runs, options.warmup) {
+            Ok(day_measurements) => measurements.extend(day_measurements),
+            Err(e) => {
+                eprintln!("{}", e);
+                ok = false;
+            }
+        }
+    }
+
+    match &baseline {
+        None => {
+            let rows = measurements.iter().map(stat_cells).collect::<Vec<_>>();
+            print_table(&["day", "phase", "min", "median", "mean", "stddev"], &rows);
+        }
+        Some(baseline) => {
+            let mut regressions = 0;
+            let rows = measurements
+                .iter()
+                .map(|m| {
+                    let [day, phase, min, median, mean, stddev] = stat_cells(m);
+                    let (before, change, flag) = compare(m, baseline, options.threshold);
+                    if flag.is_some() {
+                        regressions += 1;
+                    }
+                    [
+                        day,
+                        phase,
+                        min,
+                        median,
+                        mean,
+                        stddev,
+                        before,
+                        change,
+                        flag.unwrap_or_default().to_string(),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            print_table(
+                &[
+                    "day", "phase", "min", "median", "mean", "stddev", "baseline", "change", "",
+                ],
+                &rows,
+            );
+            if regressions > 0 {
+                println!(
+                    "\n{} regressed by more than {}% against the baseline",
+                    regressions, options.threshold
+                );
+                ok = false;
+            }
+        }
+    }
+
+    if let Some(path) = options.save_baseline {
+        if let Err(e) = bench::save_baseline(path, &measurements) {
+            eprintln!("couldn't save the baseline to {}: {}", path.display(), e);
+            ok = false;
+        }
+    }
+    ok
+}
+
+fn stat_cells(m: &Measurement) -> [String; 6] {
+    [
+        format!("day{}", m.day),
+        m.phase.to_string(),
+        format!("{:.1?}", m.stats.min),
+        format!("{:.1?}", m.stats.median),
+        format!("{:.1?}", m.stats.mean),
+        format!("{:.1?}", m.stats.stddev),
+    ]
+}
+
+/// The baseline median, the change from it, and a flag if that's a regression.
+fn compare(
+    m: &Measurement,
+    baseline: &Baseline,
+    threshold: f64,
+) -> (String, String, Option<&'static str>) {
+    match baseline.get(&(m.day, m.phase)) {
+        None => (String::new(), "new".to_string(), None),
+        // Too quick to have registered, so there's nothing to take a ratio of.
+        Some(before) if before.is_zero() => (format!("{:.1?}", before), "n/a".to_string(), None),
+        Some(before) => {
+            let change = (m.stats.median.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+            let flag = (change > threshold).then_some("REGRESSED");
+            (format!("{:.1?}", before), format!("{:+.1}%", change), flag)
+        }
+    }
+}
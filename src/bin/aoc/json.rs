@@ -0,0 +1,59 @@
+//! `--format json`: just enough JSON writing to describe runs.
+
+use advent_of_code_2021::days::{Day, Part};
+use advent_of_code_2021::solution::Run;
+
+/// Renders one record per answer as a JSON array, one record to a line.
+pub fn runs(runs: &[(&Day, Run)]) -> String {
+    let records = runs
+        .iter()
+        .flat_map(|(day, run)| {
+            run.answers.iter().map(move |(part, answer)| {
+                format!(
+                    "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}}}",
+                    day.number,
+                    match part {
+                        Part::One => 1,
+                        Part::Two => 2,
+                    },
+                    answer_value(&answer.value.to_string()),
+                    run.parse_time.as_nanos(),
+                    answer.elapsed.as_nanos()
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    if records.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", records.join(",\n"))
+    }
+}
+
+/// A string, or an array of its lines if it has more than one.
+fn answer_value(answer: &str) -> String {
+    if answer.contains('\n') {
+        let lines = answer.lines().map(string).collect::<Vec<_>>();
+        format!("[{}]", lines.join(", "))
+    } else {
+        string(answer)
+    }
+}
+
+fn string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,584 @@
+use advent_of_code_2021::client::{self, Client, Config, Fetched};
+use advent_of_code_2021::days::{self, Day, Part, DAYS};
+use advent_of_code_2021::input::{self, InputSource};
+use advent_of_code_2021::random::Rng;
+use advent_of_code_2021::solution::{self, Answer, Run};
+use advent_of_code_2021::submit::{self, History, Outcome};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+mod animate;
+mod bench;
+mod json;
+mod table;
+mod verify;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2021 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, or every day with `all`.
+    Run {
+        /// A day number, or `all`.
+        #[arg(value_parser = parse_selection)]
+        day: Selection,
+        /// Only run this part (1 or 2).
+        #[arg(long, value_parser = parse_part)]
+        part: Option<Part>,
+        /// Read the puzzle input from this file, or `-` for stdin, instead of
+        /// the one `aoc fetch` saved (or else the bundled `input.txt`). Only valid
+        /// for a single day.
+        #[arg(long)]
+        input: Option<String>,
+        /// How many days to run at once, each on its own thread.
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: u32,
+        /// How to print the answers and timings.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Draw the day's workings in the terminal before printing its answers.
+        /// Only valid for a single day, and only some days have anything to draw.
+        #[arg(long)]
+        animate: bool,
+        /// How many frames a second to draw with --animate.
+        #[arg(long, default_value_t = 10, requires = "animate", value_parser = clap::value_parser!(u32).range(1..))]
+        fps: u32,
+        /// Write the --animate frames to this file as plain text instead of
+        /// drawing them.
+        #[arg(long, requires = "animate")]
+        frames: Option<PathBuf>,
+        /// Save a picture of the day's solution to this file, as PNG if it ends
+        /// in `.png` or PPM otherwise. Only valid for a single day, and only some
+        /// days have a picture.
+        #[arg(long)]
+        image: Option<PathBuf>,
+        /// Draw each cell of the --image as a square this many pixels wide.
+        #[arg(long, default_value_t = 1, requires = "image", value_parser = clap::value_parser!(u32).range(1..))]
+        scale: u32,
+    },
+    /// Check answers against each day's stored `answers.toml`.
+    Verify {
+        /// A day number, or `all`.
+        #[arg(value_parser = parse_selection, default_value = "all")]
+        day: Selection,
+    },
+    /// Time parsing and each part over repeated runs of the fetched (or else bundled) input.
+    Bench {
+        /// A day number, or `all`.
+        #[arg(value_parser = parse_selection, default_value = "all")]
+        day: Selection,
+        /// How many timed runs of each phase.
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+        /// How many untimed runs of each phase to make first.
+        #[arg(long, default_value_t = 2)]
+        warmup: u32,
+        /// Save the median times to this file, to compare later runs against.
+        #[arg(long)]
+        save_baseline: Option<PathBuf>,
+        /// Compare against a baseline saved earlier, flagging regressions.
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// How much slower than the baseline, in percent, counts as a regression.
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Download a day's puzzle input, unless it's already been fetched.
+    ///
+    /// The input goes in `dayN/input.txt` under the cache directory rather than
+    /// next to the day's solution, so that it stays out of the source tree. The
+    /// cache directory is `$AOC_CACHE`, or else `aoc/2021` in `$XDG_CACHE_HOME`
+    /// or `~/.cache`. Once fetched, it's read in preference to the bundled input.
+    Fetch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The config file holding the session token. Defaults to `$AOC_CONFIG`,
+        /// or else `~/.config/aoc/config.toml`.
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// Submit a day's answer for one part, unless it's already known to be wrong.
+    Submit {
+        #[arg(value_parser = parse_day)]
+        day: &'static Day,
+        #[arg(value_parser = parse_part)]
+        part: Part,
+        /// Work the answer out from this file, or `-` for stdin, instead of
+        /// the input `aoc fetch` saved. The bundled input is never submitted.
+        #[arg(long)]
+        input: Option<String>,
+        /// The config file holding the session token, as for `fetch`.
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// Make up an input for a day, in its format but as big as you like.
+    Gen {
+        #[arg(value_parser = parse_day)]
+        day: &'static Day,
+        /// How big to make it. What this counts depends on the day: depths for
+        /// day1, the side of the map for day15, how deeply packets nest for
+        /// day16, lines of homework for day18, and so on.
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        scale: u64,
+        /// The same seed always makes the same input.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Write the input to this file instead of standard output.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// List every registered day.
+    List,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum Format {
+    Text,
+    /// An array of `{day, part, answer, parse_ns, solve_ns}` records.
+    Json,
+}
+
+#[derive(Clone)]
+enum Selection {
+    All,
+    Day(&'static Day),
+}
+
+impl Selection {
+    fn days(&self) -> Vec<&'static Day> {
+        match self {
+            Selection::All => DAYS.iter().collect(),
+            Selection::Day(day) => vec![day],
+        }
+    }
+
+    /// The chosen days with their fetched (or else bundled) input. `all` leaves out the
+    /// days with no input yet, which have nothing to run. A single day is always kept.
+    fn inputs(&self) -> Result<Vec<(&'static Day, Cow<'static, str>)>, String> {
+        let mut inputs = vec![];
+        let mut skipped = vec![];
+        for day in self.days() {
+            let input = InputSource::Bundled
+                .read(day)
+                .map_err(|e| format!("couldn't read input for day{}: {}", day.number, e))?;
+            if matches!(self, Selection::All) && input.trim().is_empty() {
+                skipped.push(format!("day{}", day.number));
+            } else {
+                inputs.push((day, input));
+            }
+        }
+        if !skipped.is_empty() {
+            eprintln!(
+                "skipping {}, with no input yet; see `aoc fetch`",
+                skipped.join(", ")
+            );
+        }
+        Ok(inputs)
+    }
+}
+
+fn parse_selection(s: &str) -> Result<Selection, String> {
+    if s == "all" {
+        return Ok(Selection::All);
+    }
+    let number: u8 = s
+        .parse()
+        .map_err(|_| format!("expected a day number or `all`, got `{}`", s))?;
+    days::find(number)
+        .map(Selection::Day)
+        .ok_or_else(|| format!("day{} isn't registered", number))
+}
+
+fn parse_day(s: &str) -> Result<&'static Day, String> {
+    match parse_selection(s)? {
+        Selection::Day(day) => Ok(day),
+        Selection::All => Err("expected a single day".to_string()),
+    }
+}
+
+fn parse_part(s: &str) -> Result<Part, String> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("expected 1 or 2, got `{}`", s)),
+    }
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run {
+            day,
+            part,
+            input,
+            jobs,
+            format,
+            animate,
+            fps,
+            frames,
+            image,
+            scale,
+        } => {
+            let parts = match part {
+                Some(part) => vec![part],
+                None => Part::BOTH.to_vec(),
+            };
+            let source = InputSource::from_arg(input.as_deref());
+            let inputs = match (day, source) {
+                (selection, InputSource::Bundled) => match selection.inputs() {
+                    Ok(inputs) => inputs,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return ExitCode::FAILURE;
+                    }
+                },
+                (Selection::All, _) => {
+                    eprintln!("--input can only be used when running a single day");
+                    return ExitCode::FAILURE;
+                }
+                (Selection::Day(day), source) => match source.read(day) {
+                    Ok(input) => vec![(day, input)],
+                    Err(e) => {
+                        eprintln!("couldn't read input for day{}: {}", day.number, e);
+                        return ExitCode::FAILURE;
+                    }
+                },
+            };
+
+            if animate {
+                let [(day, input)] = inputs.as_slice() else {
+                    eprintln!("--animate can only be used when running a single day");
+                    return ExitCode::FAILURE;
+                };
+                if !animate::animate(day, input, fps, frames.as_deref()) {
+                    return ExitCode::FAILURE;
+                }
+            }
+            if let Some(path) = image {
+                let [(day, input)] = inputs.as_slice() else {
+                    eprintln!("--image can only be used when running a single day");
+                    return ExitCode::FAILURE;
+                };
+                if !save_picture(day, input, &path, scale as usize) {
+                    return ExitCode::FAILURE;
+                }
+            }
+
+            let start = Instant::now();
+            let results: Box<dyn Iterator<Item = _>> = if jobs > 1 {
+                let inputs = inputs
+                    .iter()
+                    .map(|(day, input)| (*day, input.as_ref()))
+                    .collect::<Vec<_>>();
+                let results = solution::run_parallel(&inputs, &parts, jobs as usize);
+                Box::new(inputs.into_iter().map(|(day, _)| day).zip(results))
+            } else {
+                // One at a time, printing each day as soon as it's done.
+                Box::new(
+                    inputs
+                        .iter()
+                        .map(|(day, input)| (*day, solution::run(day, input, &parts))),
+                )
+            };
+
+            let mut failed = false;
+            let mut cpu_time = Duration::ZERO;
+            let mut runs = vec![];
+            for (day, result) in results {
+                match result {
+                    Ok(run) => {
+                        cpu_time += run.parse_time
+                            + run.answers.iter().map(|(_, a)| a.elapsed).sum::<Duration>();
+                        match format {
+                            Format::Text => print_run(day, &run),
+                            Format::Json => runs.push((day, run)),
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        failed = true;
+                    }
+                }
+            }
+            let wall_time = start.elapsed();
+
+            match format {
+                Format::Text if inputs.len() > 1 => println!(
+                    "\n{} days in {:?} wall-clock, {:?} summed CPU time",
+                    inputs.len(),
+                    wall_time,
+                    cpu_time
+                ),
+                Format::Text => {}
+                Format::Json => println!("{}", json::runs(&runs)),
+            }
+            if failed {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Verify { day } => {
+            let inputs = match day.inputs() {
+                Ok(inputs) => inputs,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let skipped = day
+                .days()
+                .into_iter()
+                .filter(|&day| {
+                    !inputs
+                        .iter()
+                        .any(|&(checked, _)| checked.number == day.number)
+                })
+                .collect::<Vec<_>>();
+            if !verify::verify(&inputs, &skipped) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Bench {
+            day,
+            runs,
+            warmup,
+            save_baseline,
+            baseline,
+            threshold,
+        } => {
+            let options = bench::Options {
+                runs: runs as usize,
+                warmup: warmup as usize,
+                save_baseline: save_baseline.as_deref(),
+                baseline: baseline.as_deref(),
+                threshold,
+            };
+            let inputs = match day.inputs() {
+                Ok(inputs) => inputs,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            if !bench::bench(&inputs, &options) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Fetch { day, config } => {
+            let client = match load_client(config) {
+                Ok(client) => client,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let Some(dir) = days::cache_directory(day) else {
+                eprintln!("couldn't find a cache directory; set $AOC_CACHE");
+                return ExitCode::FAILURE;
+            };
+            match client::fetch_into(&client, day, &dir) {
+                Ok(Fetched::AlreadyCached) => {
+                    println!("day{}'s input is already in {}", day, dir.display())
+                }
+                Ok(Fetched::Downloaded) => println!(
+                    "saved day{}'s input to {}",
+                    day,
+                    dir.join("input.txt").display()
+                ),
+                Err(e) => {
+                    eprintln!("couldn't fetch day{}'s input: {}", day, e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::Submit {
+            day,
+            part,
+            input,
+            config,
+        } => {
+            if !submit(day, part, input.as_deref(), config) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Gen {
+            day,
+            scale,
+            seed,
+            output,
+        } => {
+            if !generate(day, scale as usize, seed, output.as_deref()) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::List => DAYS
+            .iter()
+            .for_each(|day| println!("day{:<2} {}", day.number, day.title)),
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Works out the answer from the fetched input (or `input`, if given) and
+/// submits it, recording the attempt in `submissions.tsv` in the day's cache
+/// directory. Returns whether it was right.
+fn submit(day: &Day, part: Part, input: Option<&str>, config: Option<PathBuf>) -> bool {
+    let input = match InputSource::from_arg(input) {
+        InputSource::Bundled => input::fetched(day).map(|input| input.map(Cow::Owned)),
+        source => source.read(day).map(Some),
+    };
+    let input = match input {
+        Ok(Some(input)) => input,
+        Ok(None) => {
+            eprintln!(
+                "day{} has no fetched input to submit an answer for; run `aoc fetch {}` or pass --input",
+                day.number, day.number
+            );
+            return false;
+        }
+        Err(e) => {
+            eprintln!("couldn't read input for day{}: {}", day.number, e);
+            return false;
+        }
+    };
+    let run = match solution::run(day, &input, &[part]) {
+        Ok(run) => run,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+    let answer = match &run.answers[0].1.value {
+        Answer::Found(answer) => answer,
+        Answer::Impossible(why) => {
+            eprintln!(
+                "day{} {} has no answer to submit: {}",
+                day.number, part, why
+            );
+            return false;
+        }
+    };
+    let client = match load_client(config) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+    let Some(dir) = days::cache_directory(day.number) else {
+        eprintln!("couldn't find a cache directory; set $AOC_CACHE");
+        return false;
+    };
+    let path = dir.join("submissions.tsv");
+    println!(
+        "day{} {}: submitting {}",
+        day.number,
+        part,
+        days::format_answer(answer)
+    );
+    let result = History::load(&path).and_then(|mut history| {
+        submit::submit(
+            &client,
+            &mut history,
+            day.number,
+            part,
+            answer,
+            submit::now(),
+        )
+    });
+    match result {
+        Ok(Ok(outcome)) => {
+            println!("{}", outcome);
+            outcome == Outcome::Right
+        }
+        Ok(Err(refusal)) => {
+            println!("not submitted: {}", refusal);
+            false
+        }
+        Err(e) => {
+            eprintln!("couldn't submit: {}", e);
+            false
+        }
+    }
+}
+
+/// Makes up an input for the day and writes it to `output`, or to standard
+/// output if there isn't one. Returns whether that worked.
+fn generate(day: &Day, scale: usize, seed: u64, output: Option<&Path>) -> bool {
+    let input = (day.generate)(&mut Rng::new(seed), scale);
+    let (result, destination) = match output {
+        Some(path) => (fs::write(path, &input), path.display().to_string()),
+        None => (
+            io::stdout().lock().write_all(input.as_bytes()),
+            "standard output".to_string(),
+        ),
+    };
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!(
+                "couldn't write day{}'s input to {}: {}",
+                day.number, destination, e
+            );
+            false
+        }
+    }
+}
+
+/// Draws the day's picture of `input` and saves it to `path`. Returns whether
+/// that worked.
+fn save_picture(day: &Day, input: &str, path: &Path, scale: usize) -> bool {
+    let Some(picture) = day.picture else {
+        eprintln!("day{} has no picture", day.number);
+        return false;
+    };
+    let image = match picture(input) {
+        Ok(image) => image.scaled(scale),
+        Err(e) => {
+            eprintln!("{}", e.in_day(day.number));
+            return false;
+        }
+    };
+    match image.save(path) {
+        Ok(()) => {
+            println!("saved day{}'s picture to {}", day.number, path.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("couldn't save {}: {}", path.display(), e);
+            false
+        }
+    }
+}
+
+/// A client using the session token from the given config file, or the default one.
+fn load_client(config: Option<PathBuf>) -> Result<Client, String> {
+    let path = config
+        .or_else(Config::default_path)
+        .ok_or("no config file given, and neither $AOC_CONFIG nor $HOME is set")?;
+    Config::load(&path)
+        .map(Client::new)
+        .map_err(|e| format!("couldn't load the config from {}: {}", path.display(), e))
+}
+
+/// Prints the answers from one day's run, with how long each step took.
+fn print_run(day: &Day, run: &Run) {
+    println!("day{} parsed in {:?}", day.number, run.parse_time);
+    for (part, answer) in &run.answers {
+        println!(
+            "day{} {} result: {} ({:?})",
+            day.number,
+            part,
+            days::format_answer(&answer.value.to_string()),
+            answer.elapsed
+        );
+    }
+}
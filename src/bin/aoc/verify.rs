@@ -0,0 +1,144 @@
+//! `aoc verify`: checking answers against each day's `answers.toml`.
+
+use crate::table::print_table;
+use advent_of_code_2021::days::{Day, Part};
+use advent_of_code_2021::solution;
+use std::borrow::Cow;
+
+/// How one part's answer compares with the stored one.
+#[derive(PartialEq)]
+enum Verdict {
+    Ok,
+    Mismatch,
+    Missing,
+    /// The day has no input to run, so its answers weren't checked.
+    Skipped,
+}
+
+impl Verdict {
+    fn label(&self) -> &'static str {
+        match self {
+            Verdict::Ok => "ok",
+            Verdict::Mismatch => "MISMATCH",
+            Verdict::Missing => "missing",
+            Verdict::Skipped => "skipped",
+        }
+    }
+}
+
+struct Check {
+    day: u8,
+    part: Part,
+    verdict: Verdict,
+    expected: Option<String>,
+    actual: String,
+}
+
+/// Runs each day on its input and prints a table comparing the answers with the
+/// stored ones, listing the `skipped` days as unchecked. Returns whether every
+/// answer that was checked was there and matched.
+pub fn verify(inputs: &[(&Day, Cow<str>)], skipped: &[&Day]) -> bool {
+    let mut checks = vec![];
+    let mut errors = 0;
+    for day in skipped {
+        for part in Part::BOTH {
+            checks.push(Check {
+                day: day.number,
+                part,
+                verdict: Verdict::Skipped,
+                expected: None,
+                actual: "no input".to_string(),
+            });
+        }
+    }
+    for (day, input) in inputs {
+        let results = day
+            .answers()
+            .and_then(|answers| Ok((answers, solution::run(day, input, &Part::BOTH)?)));
+        let (answers, run) = match results {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!("{}", e);
+                errors += 1;
+                continue;
+            }
+        };
+        for (part, actual) in run.answers {
+            let actual = actual.value.to_string();
+            let expected = answers.get(part).map(String::from);
+            let verdict = match &expected {
+                None => Verdict::Missing,
+                Some(expected) if *expected == actual => Verdict::Ok,
+                Some(_) => Verdict::Mismatch,
+            };
+            checks.push(Check {
+                day: day.number,
+                part,
+                verdict,
+                expected,
+                actual,
+            });
+        }
+    }
+
+    checks.sort_by_key(|check| (check.day, check.part == Part::Two));
+    let rows = checks
+        .iter()
+        .map(|check| {
+            [
+                format!("day{}", check.day),
+                check.part.to_string(),
+                check.verdict.label().to_string(),
+                check.expected.as_deref().map_or(String::new(), summarise),
+                summarise(&check.actual),
+            ]
+        })
+        .collect::<Vec<_>>();
+    print_table(&["day", "part", "status", "expected", "actual"], &rows);
+
+    // Multi-line answers only fit in the table as a summary, so show them in full.
+    for check in &checks {
+        if let (Verdict::Mismatch, Some(expected)) = (&check.verdict, &check.expected) {
+            if expected.contains('\n') || check.actual.contains('\n') {
+                println!(
+                    "\nday{} {} expected:\n{}\nbut got:\n{}",
+                    check.day, check.part, expected, check.actual
+                );
+            }
+        }
+    }
+
+    let count = |verdict: Verdict| {
+        checks
+            .iter()
+            .filter(|check| check.verdict == verdict)
+            .count()
+    };
+    println!(
+        "\n{} ok, {} mismatched, {} missing, {} failed to run, {} skipped",
+        count(Verdict::Ok),
+        count(Verdict::Mismatch),
+        count(Verdict::Missing),
+        errors,
+        count(Verdict::Skipped)
+    );
+    if !skipped.is_empty() {
+        let days = skipped.iter().map(|day| format!("day{}", day.number));
+        println!(
+            "not checked, with no input yet: {}",
+            days.collect::<Vec<_>>().join(", ")
+        );
+    }
+    checks
+        .iter()
+        .all(|check| matches!(check.verdict, Verdict::Ok | Verdict::Skipped))
+        && errors == 0
+}
+
+/// A one-line stand-in for an answer, since multi-line ones would break up the table.
+fn summarise(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_string(),
+        lines => format!("<{} lines>", lines),
+    }
+}
@@ -0,0 +1,5 @@
+fn main() -> std::io::Result<()> {
+    advent_of_code_2021::days::find(21)
+        .unwrap()
+        .run_standalone()
+}
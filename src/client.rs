@@ -0,0 +1,218 @@
+//! Talking to the Advent of Code site, with the session token from a config file.
+
+use crate::days::Part;
+use crate::error::ParseError;
+#[cfg(test)]
+use crate::http::stand_in_server;
+use crate::http::{self, Response};
+use crate::submit::Outcome;
+use crate::toml;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u32 = 2021;
+const USER_AGENT: &str = "advent_of_code_2021 aoc runner";
+
+/// The contents of the config file, a `session` token copied from the site's
+/// cookie and, for testing against something else, a `base_url`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub session: String,
+    pub base_url: String,
+}
+
+impl Config {
+    /// `$AOC_CONFIG` if it's set, or else `~/.config/aoc/config.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("AOC_CONFIG").map(PathBuf::from).or_else(|| {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/aoc/config.toml"))
+        })
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut session = None;
+        let mut base_url = None;
+        for entry in toml::entries(text)? {
+            match entry.key {
+                "session" => session = Some(entry.value),
+                "base_url" => base_url = Some(entry.value.trim_end_matches('/').to_string()),
+                _ => return Err(entry.unexpected_key("`session` or `base_url`")),
+            }
+        }
+        Ok(Config {
+            session: session
+                .ok_or_else(|| ParseError::unexpected_end(text, "expected a `session` key"))?,
+            base_url: base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+        })
+    }
+}
+
+pub struct Client {
+    config: Config,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        Client { config }
+    }
+
+    /// Downloads a day's puzzle input.
+    pub fn fetch_input(&self, day: u8) -> io::Result<String> {
+        let response = self.request("GET", day, "/input", None)?;
+        match response.status {
+            200 if !response.body.is_empty() => Ok(response.body),
+            200 => Err(io::Error::other(format!("day{}'s input was empty", day))),
+            404 => Err(io::Error::other(format!("day{} isn't unlocked yet", day))),
+            400 | 500 => Err(io::Error::other(format!(
+                "the session token was rejected (status {})",
+                response.status
+            ))),
+            status => Err(io::Error::other(format!(
+                "unexpected status {} fetching day{}'s input",
+                status, day
+            ))),
+        }
+    }
+
+    /// Posts an answer and reads what the site made of it.
+    pub fn submit_answer(&self, day: u8, part: Part, answer: &str) -> io::Result<Outcome> {
+        let level = match part {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+        let body = format!("level={}&answer={}", level, form_encode(answer));
+        let response = self.request("POST", day, "/answer", Some(&body))?;
+        match response.status {
+            200 => Ok(Outcome::from_response(&response.body)),
+            status => Err(io::Error::other(format!(
+                "unexpected status {} submitting day{} {}",
+                status, day, part
+            ))),
+        }
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        day: u8,
+        path: &str,
+        body: Option<&str>,
+    ) -> io::Result<Response> {
+        let url = format!("{}/{}/day/{}{}", self.config.base_url, YEAR, day, path);
+        let cookie = format!("session={}", self.config.session);
+        http::request(
+            method,
+            &url,
+            &[("Cookie", &cookie), ("User-Agent", USER_AGENT)],
+            body,
+        )
+    }
+}
+
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'-' | b'.' | b'_' => (b as char).to_string(),
+            b' ' => "+".to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Whether [`fetch_into`] had to download anything.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fetched {
+    AlreadyCached,
+    Downloaded,
+}
+
+/// Saves a day's input as `input.txt` in `dir`, unless it's already there: inputs
+/// never change, so there's no need to ask the site twice.
+pub fn fetch_into(client: &Client, day: u8, dir: &Path) -> io::Result<Fetched> {
+    let path = dir.join("input.txt");
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(Fetched::AlreadyCached);
+    }
+    let input = client.fetch_input(day)?;
+    fs::create_dir_all(dir)?;
+    fs::write(path, input)?;
+    Ok(Fetched::Downloaded)
+}
+
+/// A fresh, empty directory to test with.
+#[cfg(test)]
+pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[cfg(test)]
+fn stand_in_client(base_url: String) -> Client {
+    Client::new(Config {
+        session: "53cr37".to_string(),
+        base_url,
+    })
+}
+
+#[test]
+fn config() {
+    let config = Config::parse("# from the browser\nsession = \"abc123\"\n").unwrap();
+    assert_eq!("abc123", config.session);
+    assert_eq!(DEFAULT_BASE_URL, config.base_url);
+
+    let config = Config::parse("session = \"abc\"\nbase_url = \"http://localhost:8080/\"").unwrap();
+    assert_eq!("http://localhost:8080", config.base_url);
+
+    assert!(Config::parse("base_url = \"http://localhost\"").is_err());
+}
+
+#[test]
+fn fetches_once_then_uses_the_cache() {
+    let (base_url, server) = stand_in_server(vec![(200, "1\n2\n3\n")]);
+    let client = stand_in_client(base_url);
+    let dir = scratch_dir("fetch");
+
+    assert_eq!(Fetched::Downloaded, fetch_into(&client, 7, &dir).unwrap());
+    // The stand-in only answers once, so this would fail if it asked again.
+    assert_eq!(
+        Fetched::AlreadyCached,
+        fetch_into(&client, 7, &dir).unwrap()
+    );
+    assert_eq!(
+        "1\n2\n3\n",
+        fs::read_to_string(dir.join("input.txt")).unwrap()
+    );
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /2021/day/7/input HTTP/1.0\r\n"));
+    assert!(requests[0].contains("Cookie: session=53cr37\r\n"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn fetch_failures_leave_no_file() {
+    let (base_url, server) = stand_in_server(vec![
+        (
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        ),
+        (400, ""),
+    ]);
+    let client = stand_in_client(base_url);
+    let dir = scratch_dir("fetch-failures");
+
+    let locked = fetch_into(&client, 25, &dir).unwrap_err();
+    assert_eq!("day25 isn't unlocked yet", locked.to_string());
+    let rejected = fetch_into(&client, 1, &dir).unwrap_err();
+    assert!(rejected.to_string().contains("session token was rejected"));
+    assert!(!dir.join("input.txt").exists());
+    server.join().unwrap();
+}
@@ -0,0 +1,41 @@
+//! Colours shared by the terminal animations and the image files.
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour::rgb(0, 0, 0);
+    pub const GREY: Colour = Colour::rgb(100, 100, 100);
+    pub const WHITE: Colour = Colour::rgb(255, 255, 255);
+    pub const RED: Colour = Colour::rgb(220, 50, 47);
+    pub const YELLOW: Colour = Colour::rgb(250, 220, 80);
+    pub const BLUE: Colour = Colour::rgb(38, 90, 210);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Colour { r, g, b }
+    }
+
+    /// The colour `fraction` of the way from this one to `other`.
+    pub fn towards(self, other: Colour, fraction: f64) -> Colour {
+        let mix = |from: u8, to: u8| {
+            (from as f64 + (to as f64 - from as f64) * fraction.clamp(0.0, 1.0)).round() as u8
+        };
+        Colour::rgb(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+}
+
+#[test]
+fn blends() {
+    assert_eq!(
+        Colour::rgb(128, 128, 128),
+        Colour::BLACK.towards(Colour::WHITE, 0.5)
+    );
+}
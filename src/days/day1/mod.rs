@@ -0,0 +1,69 @@
+use crate::error::ParseError;
+use crate::parse::{self, integer};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use itertools::Itertools;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        parse::lines(input, integer("a depth"))
+    }
+
+    fn part1(report: &Vec<u32>) -> u32 {
+        count_increases(report)
+    }
+
+    fn part2(report: &Vec<u32>) -> u32 {
+        // Three depths can add up to more than a u32 holds.
+        let window_sums: Vec<u64> = report
+            .windows(3)
+            .map(|window| window.iter().map(|&depth| depth as u64).sum())
+            .collect();
+        count_increases(&window_sums)
+    }
+}
+
+fn count_increases<T: PartialOrd>(list_of_things: &[T]) -> u32 {
+    list_of_things
+        .iter()
+        .tuple_windows()
+        .map(|(prev, curr)| (curr > prev) as u32)
+        .sum()
+}
+
+/// `scale` depths, wandering mostly deeper.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut depth = rng.range(100..200);
+    (0..scale)
+        .map(|_| {
+            depth = (depth + rng.range(-10..30)).max(0);
+            format!("{}\n", depth)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+#[test]
+fn example() {
+    assert_eq!((7, 5), solve::<Day1>(EXAMPLE));
+}
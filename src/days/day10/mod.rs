@@ -0,0 +1,173 @@
+use crate::error::{parse_lines, ParseError};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::{Answer, Solution};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = Answer<usize>;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        if input.trim().is_empty() {
+            return Err(ParseError::unexpected_end(
+                input,
+                "expected a line of brackets",
+            ));
+        }
+        parse_lines(input, |lineno, line| {
+            match line.chars().position(|c| !"()[]{}<>".contains(c)) {
+                Some(column) => Err(ParseError::at_column(
+                    lineno,
+                    line,
+                    column + 1,
+                    "expected a bracket",
+                )),
+                None => Ok(line.to_string()),
+            }
+        })
+    }
+
+    fn part1(lines: &Vec<String>) -> usize {
+        lines.iter().filter_map(|line| parse_line(line).err()).sum()
+    }
+
+    fn part2(lines: &Vec<String>) -> Answer<usize> {
+        let scores = lines
+            .iter()
+            .filter_map(|line| parse_line(line).ok())
+            .map(calculate_leftover_score)
+            .collect::<Option<Vec<usize>>>();
+        let Some(mut scores) = scores else {
+            return Answer::Impossible("a line's score is too big to count".to_string());
+        };
+        scores.sort();
+        Answer::found_or(
+            scores.get(scores.len() / 2).copied(),
+            "every line is corrupted",
+        )
+    }
+}
+
+fn parse_line(line: &str) -> Result<Vec<char>, usize> {
+    let mut parse_stack = vec![];
+
+    for b in line.chars() {
+        match b {
+            '(' | '[' | '{' | '<' => parse_stack.push(b),
+            ')' => {
+                if let Some('(') = parse_stack.pop() {
+                } else {
+                    return Err(3);
+                }
+            }
+            ']' => {
+                if let Some('[') = parse_stack.pop() {
+                } else {
+                    return Err(57);
+                }
+            }
+            '}' => {
+                if let Some('{') = parse_stack.pop() {
+                } else {
+                    return Err(1197);
+                }
+            }
+            '>' => {
+                if let Some('<') = parse_stack.pop() {
+                } else {
+                    return Err(25137);
+                }
+            }
+            c => panic!("unexpected input: {}", c),
+        }
+    }
+
+    Ok(parse_stack)
+}
+
+/// The score for closing what's left open, or `None` if it overflows, which takes
+/// about 28 brackets.
+fn calculate_leftover_score(mut remainder: Vec<char>) -> Option<usize> {
+    let mut score: usize = 0;
+
+    while let Some(b) = remainder.pop() {
+        score = score.checked_mul(5)?.checked_add(match b {
+            '(' => 1,
+            '[' => 2,
+            '{' => 3,
+            '<' => 4,
+            c => panic!("unexpected character in remainder {}", c),
+        })?;
+    }
+
+    Some(score)
+}
+
+/// `scale` lines of brackets, each either corrupted or incomplete.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+    (0..scale)
+        .map(|_| {
+            let corrupted = rng.chance(0.5);
+            let mut line = String::new();
+            let mut open = vec![];
+            for _ in 0..rng.range(10..100) {
+                if open.is_empty() || rng.chance(0.55) {
+                    let (opener, closer) = *rng.pick(&PAIRS);
+                    line.push(opener);
+                    open.push(closer);
+                } else {
+                    line.push(open.pop().unwrap());
+                }
+            }
+            if corrupted {
+                // Close something other than what's open.
+                let expected = open.pop().unwrap_or(')');
+                let wrong = PAIRS
+                    .iter()
+                    .map(|&(_, closer)| closer)
+                    .find(|&c| c != expected);
+                line.push(wrong.unwrap());
+            } else if open.is_empty() {
+                line.push('(');
+            }
+            line + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+#[test]
+fn example() {
+    assert_eq!((26397, Answer::Found(288957)), solve::<Day10>(EXAMPLE));
+}
+
+#[test]
+fn no_answer_with_only_corrupted_lines() {
+    let why = "every line is corrupted".to_string();
+    assert_eq!((57, Answer::Impossible(why)), solve::<Day10>("(]"));
+    assert!(Day10::parse("\n").is_err());
+}
+
+#[test]
+fn no_answer_once_a_score_overflows() {
+    let why = "a line's score is too big to count".to_string();
+    let lines = format!("{}\n[]\n", "<".repeat(28));
+    assert_eq!((0, Answer::Impossible(why)), solve::<Day10>(&lines));
+}
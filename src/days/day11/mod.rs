@@ -0,0 +1,210 @@
+use crate::animate::{Cell, Frame};
+use crate::automaton::{self, Cycle};
+use crate::colour::Colour;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::{Answer, Solution};
+use rusttype::Point;
+use std::collections::{HashMap, HashSet};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Octogrid;
+    type Answer1 = usize;
+    type Answer2 = Answer<usize>;
+
+    fn parse(input: &str) -> Result<Octogrid, ParseError> {
+        Octogrid::from_input(input)
+    }
+
+    fn part1(starting_grid: &Octogrid) -> usize {
+        let mut grid = starting_grid.clone();
+        let mut result = 0;
+        automaton::run(&mut grid, 100, |grid| result += grid.step());
+        result
+    }
+
+    fn part2(starting_grid: &Octogrid) -> Answer<usize> {
+        Answer::found_or(
+            first_synchronised(&starting_grid.cycle()),
+            NEVER_SYNCHRONISED,
+        )
+    }
+}
+
+const NEVER_SYNCHRONISED: &str = "the octopuses never all flash at once";
+
+/// The first step after which every octopus has just flashed, if any ever is.
+fn first_synchronised(cycle: &Cycle<Octogrid>) -> Option<usize> {
+    // Going one step past the states kept comes back round to the loop's start.
+    (1..=cycle.states.len()).find(|&step| cycle.state_at(step).all_flashed())
+}
+
+/// Steps the octopuses until they all flash together, or until they're back where
+/// they were if they never do, with a frame for each step.
+pub fn animate(input: &str, show: &mut dyn FnMut(Frame)) -> Result<(), ParseError> {
+    let mut grid = Day11::parse(input)?;
+    let cycle = grid.cycle();
+    let steps = first_synchronised(&cycle).unwrap_or(cycle.states.len());
+    show(grid.frame("step 0".to_string()));
+    let mut taken = 0;
+    automaton::run(&mut grid, steps, |grid| {
+        let flashes = grid.step();
+        taken += 1;
+        show(grid.frame(format!("step {}: {} flashes", taken, flashes)));
+    });
+    Ok(())
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Octogrid {
+    grid: Grid<u8>,
+}
+
+impl Octogrid {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse_digits(input, "an energy level")?;
+        if grid.width() != SIZE {
+            let first_line = input.lines().next().unwrap_or("");
+            return Err(ParseError::new(
+                1,
+                first_line,
+                first_line,
+                format!("expected {} octopuses", SIZE),
+            ));
+        }
+        if grid.height() != SIZE {
+            return Err(ParseError::unexpected_end(
+                input,
+                format!("expected {} rows of octopuses", SIZE),
+            ));
+        }
+
+        Ok(Octogrid { grid })
+    }
+
+    fn step(&mut self) -> usize {
+        let mut flashees: HashMap<Point<usize>, usize> = HashMap::new();
+        let mut flashers: HashSet<Point<usize>> = HashSet::new();
+
+        self.grid.values_mut().for_each(|oct| *oct += 1);
+
+        loop {
+            for pos in self.grid.positions() {
+                if self.grid[pos] > 9 {
+                    assert!(!flashers.contains(&pos));
+                    self.grid.neighbours8(pos).for_each(|neighbour| {
+                        *flashees.entry(neighbour).or_default() += 1;
+                    });
+                    flashers.insert(pos);
+                    self.grid[pos] = 0;
+                }
+            }
+
+            flashees.retain(|pos, _| !flashers.contains(pos));
+
+            if flashees.is_empty() {
+                break;
+            }
+
+            flashees
+                .drain()
+                .for_each(|(pos, count)| self.grid[pos] += count as u8);
+        }
+
+        flashers.len()
+    }
+
+    /// Octopuses that just flashed glow, and the rest brighten as they charge.
+    fn frame(&self, caption: String) -> Frame {
+        let cells = self.grid.map(|&energy| match energy {
+            0 => Cell::new('0', Colour::WHITE),
+            _ => Cell::new(
+                char::from(b'0' + energy),
+                Colour::BLUE.towards(Colour::YELLOW, energy as f64 / 9.0),
+            ),
+        });
+        Frame::new(caption, cells)
+    }
+
+    /// Every state the octopuses go through before they repeat themselves.
+    fn cycle(&self) -> Cycle<Octogrid> {
+        automaton::find_cycle(self.clone(), |grid| {
+            grid.step();
+        })
+    }
+
+    /// Whether every octopus flashed in the last step, which leaves them all at 0.
+    fn all_flashed(&self) -> bool {
+        self.grid.values().all(|&energy| energy == 0)
+    }
+}
+
+const SIZE: usize = 10;
+
+/// A grid of octopuses that do all flash together at some point, which most
+/// random grids never manage. There are always ten by ten of them, so `scale`
+/// is ignored.
+pub fn generate(rng: &mut Rng, _scale: usize) -> String {
+    loop {
+        let input = Grid::from_fn(SIZE, SIZE, |_| rng.below(10)).to_string() + "\n";
+        let grid = Octogrid::from_input(&input).expect("generated a valid grid");
+        if first_synchronised(&grid.cycle()).is_some() {
+            return input;
+        }
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+#[test]
+fn example() {
+    assert_eq!((1656, Answer::Found(195)), solve::<Day11>(EXAMPLE));
+}
+
+#[test]
+fn no_answer_when_never_synchronised() {
+    // Goes round a loop of 7 steps from step 145 without ever all flashing.
+    let grid = "\
+3197652022
+0121280787
+8617854732
+9978963464
+7296895788
+6331052704
+3411358251
+3735534552
+8187280703
+9164292183";
+    let grid = Day11::parse(grid).unwrap();
+    assert_eq!(
+        Answer::Impossible(NEVER_SYNCHRONISED.to_string()),
+        Day11::part2(&grid)
+    );
+}
+
+#[test]
+fn fast_forwards_past_the_synchronised_flash() {
+    // They all flash at step 195 and every ten steps after, so by step 10^12
+    // they've counted up to 5 since the last flash at step 10^12 - 5.
+    let grid = Day11::parse(EXAMPLE).unwrap();
+    let grid = automaton::fast_forward(grid, 1_000_000_000_000, |grid| {
+        grid.step();
+    });
+    assert!(grid.grid.values().all(|&energy| energy == 5));
+}
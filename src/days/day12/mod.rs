@@ -0,0 +1,194 @@
+use crate::error::ParseError;
+use crate::parse::{self, check, pair, word};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use itertools::Itertools;
+use linked_hash_map::LinkedHashMap;
+use std::collections::{BTreeMap, HashMap};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = CaveMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<CaveMap, ParseError> {
+        CaveMap::from_input(input)
+    }
+
+    fn part1(map: &CaveMap) -> usize {
+        map.count_paths(
+            |cave, path| cave.chars().all(|c| c.is_uppercase()) || !path.contains_key(cave),
+            |cave| cave == "end",
+        )
+    }
+
+    fn part2(map: &CaveMap) -> usize {
+        map.count_paths(
+            |cave, path| {
+                cave != "start"
+                    && (cave.chars().all(|c| c.is_uppercase())
+                        || path.values().all(|x| *x < 2)
+                        || !path.contains_key(cave))
+            },
+            |cave| cave == "end",
+        )
+    }
+}
+
+pub struct CaveMap {
+    connections: HashMap<String, Vec<String>>,
+}
+
+impl CaveMap {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let mut connections: HashMap<String, Vec<String>> = HashMap::new();
+
+        let is_big = |cave: &str| cave.chars().all(|c| c.is_ascii_uppercase());
+        let cave = || {
+            check(word("a cave"), |&cave: &&str| {
+                if !(is_big(cave) || cave.chars().all(|c| c.is_ascii_lowercase())) {
+                    return Err("expected a cave name in all upper or all lower case".to_string());
+                }
+                Ok(())
+            })
+        };
+        let connection = check(pair(cave(), "-", cave()), |&(a, b)| {
+            if is_big(a) && is_big(b) {
+                // Two adjacent big caves could be bounced between forever.
+                return Err("big caves can't be connected to each other".to_string());
+            }
+            Ok(())
+        });
+        let pairs = parse::lines(input, connection)?;
+
+        for (a, b) in pairs {
+            connections
+                .entry(a.to_string())
+                .or_default()
+                .push(b.to_string());
+            connections
+                .entry(b.to_string())
+                .or_default()
+                .push(a.to_string());
+        }
+
+        if !connections.contains_key("start") {
+            return Err(ParseError::unexpected_end(input, "expected a `start` cave"));
+        }
+        Ok(CaveMap { connections })
+    }
+
+    fn count_paths<Pv, Pf>(&self, valid_move: Pv, final_move: Pf) -> usize
+    where
+        Pv: Fn(&String, &BTreeMap<String, u8>) -> bool,
+        Pf: Fn(&String) -> bool,
+    {
+        let mut path_count = 0;
+        let mut paths = LinkedHashMap::new();
+        paths.insert(
+            (
+                BTreeMap::from([("start".to_string(), 1u8)]),
+                "start".to_string(),
+            ),
+            1usize,
+        );
+
+        while !paths.is_empty() {
+            let (current_path, count) = paths.pop_front().unwrap();
+            for cave in &self.connections[&current_path.1] {
+                if final_move(cave) {
+                    path_count += count;
+                } else if valid_move(cave, &current_path.0) {
+                    let mut continued_path = current_path.clone();
+                    if cave.chars().all(|c| c.is_lowercase()) {
+                        *continued_path.0.entry(cave.to_string()).or_default() += 1;
+                    }
+                    continued_path.1 = cave.clone();
+                    if paths.contains_key(&continued_path) {
+                        paths[&continued_path] += count;
+                    } else {
+                        paths.insert(continued_path, count);
+                    }
+                }
+            }
+        }
+
+        path_count
+    }
+}
+
+/// A cave system with `scale` small caves besides `start` and `end`, and a big
+/// cave for every three small ones.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let name = |i: usize| {
+        format!(
+            "{}{}",
+            (b'a' + (i / 26 % 26) as u8) as char,
+            (b'a' + (i % 26) as u8) as char
+        )
+    };
+    let mut small = (0..scale).map(name).collect_vec();
+    let big = (0..scale.div_ceil(3))
+        .map(|i| name(i).to_uppercase())
+        .collect_vec();
+    let mut edges: Vec<(String, String)> = vec![];
+    let mut connect = |a: &String, b: &String| {
+        if a != b
+            && !edges.contains(&(a.clone(), b.clone()))
+            && !edges.contains(&(b.clone(), a.clone()))
+        {
+            edges.push((a.clone(), b.clone()));
+        }
+    };
+
+    small.extend(["start".to_string(), "end".to_string()]);
+    let caves = small.iter().chain(&big).cloned().collect_vec();
+    for cave in &small {
+        for _ in 0..rng.range(1..3) {
+            connect(cave, rng.pick(&caves));
+        }
+    }
+    for cave in &big {
+        // Big caves can't lead to each other.
+        for _ in 0..rng.range(2..4) {
+            connect(cave, rng.pick(&small));
+        }
+    }
+    edges
+        .iter()
+        .map(|(a, b)| format!("{}-{}\n", a, b))
+        .collect()
+}
+
+#[test]
+fn small_example() {
+    let input = "\
+start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+    assert_eq!((10, 36), solve::<Day12>(input));
+}
+
+#[test]
+fn larger_example() {
+    let input = "\
+dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc";
+    assert_eq!((19, 103), solve::<Day12>(input));
+}
@@ -0,0 +1,267 @@
+use crate::animate::{Cell, Frame};
+use crate::colour::Colour;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::image::Image;
+use crate::ocr;
+use crate::parse::{self, check, choice, integer, pair, preceded};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use rusttype::Point;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Manual;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Manual, ParseError> {
+        let blocks = parse::blocks(input);
+        let (dots, folds) = match blocks.split_first() {
+            Some((dots, folds)) if !folds.is_empty() => (dots, folds),
+            _ => {
+                return Err(ParseError::unexpected_end(
+                    input,
+                    "expected a fold instruction",
+                ))
+            }
+        };
+
+        let bounded = |expected, what| {
+            check(integer(expected), move |&n: &usize| {
+                if n > MAX_COORDINATE {
+                    return Err(format!("{} only go up to {}", what, MAX_COORDINATE));
+                }
+                Ok(())
+            })
+        };
+        let coordinate = || bounded("a coordinate", "coordinates");
+        let dots = dots.parse_lines(pair(coordinate(), ",", coordinate()))?;
+        let paper = DottedPaper::from_dots(&dots);
+
+        let axis = choice(&[("x", Direction::X), ("y", Direction::Y)]);
+        let position = bounded("a fold position", "fold positions");
+        let fold = preceded("fold along ", pair(axis, "=", position));
+        let instructions = folds
+            .iter()
+            .map(|block| block.parse_lines(&fold))
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+
+        Ok(Manual {
+            paper,
+            instructions,
+        })
+    }
+
+    fn part1(manual: &Manual) -> usize {
+        let mut _paper = manual.paper.clone();
+        let (dir, pos) = manual.instructions[0];
+        _paper.make_fold(dir, pos);
+        _paper.dot_count()
+    }
+
+    /// The letters the dots spell out once folded, or a drawing of the dots if
+    /// they aren't letters.
+    fn part2(manual: &Manual) -> String {
+        let paper = fold_completely(manual);
+        ocr::read(&paper.grid()).unwrap_or_else(|| paper.to_string())
+    }
+}
+
+fn fold_completely(manual: &Manual) -> DottedPaper {
+    let mut paper = manual.paper.clone();
+    manual
+        .instructions
+        .iter()
+        .for_each(|&(dir, pos)| paper.make_fold(dir, pos));
+    paper
+}
+
+/// Makes each fold in turn, with a frame for each.
+pub fn animate(input: &str, show: &mut dyn FnMut(Frame)) -> Result<(), ParseError> {
+    let manual = Day13::parse(input)?;
+    let mut paper = manual.paper.clone();
+    show(paper.frame(format!("unfolded: {} dots", paper.dot_count())));
+    for (i, &(dir, pos)) in manual.instructions.iter().enumerate() {
+        paper.make_fold(dir, pos);
+        let axis = match dir {
+            Direction::X => 'x',
+            Direction::Y => 'y',
+        };
+        show(paper.frame(format!(
+            "fold {} along {}={}: {} dots",
+            i + 1,
+            axis,
+            pos,
+            paper.dot_count()
+        )));
+    }
+    Ok(())
+}
+
+/// The paper once every fold has been made, with the dots in white.
+pub fn picture(input: &str) -> Result<Image, ParseError> {
+    let paper = fold_completely(&Day13::parse(input)?);
+    Ok(Image::new(paper.grid().map(|&dot| match dot {
+        true => Colour::WHITE,
+        false => Colour::BLACK,
+    })))
+}
+
+/// The transparent paper and the folds to make in it.
+pub struct Manual {
+    paper: DottedPaper,
+    instructions: Vec<(Direction, usize)>,
+}
+
+/// The dots on a sheet of paper `width` by `height`, which is as big as the
+/// furthest dot until it's folded.
+#[derive(Clone)]
+pub struct DottedPaper {
+    dots: HashSet<Point<usize>>,
+    width: usize,
+    height: usize,
+}
+
+impl DottedPaper {
+    fn from_dots(dots: &[(usize, usize)]) -> Self {
+        DottedPaper {
+            dots: dots.iter().map(|&(x, y)| Point { x, y }).collect(),
+            width: dots.iter().map(|&(x, _)| x + 1).max().unwrap_or(0),
+            height: dots.iter().map(|&(_, y)| y + 1).max().unwrap_or(0),
+        }
+    }
+
+    fn dot_count(&self) -> usize {
+        self.dots.len()
+    }
+
+    /// The whole sheet, dots and all.
+    fn grid(&self) -> Grid<bool> {
+        Grid::from_fn(self.width, self.height, |point| self.dots.contains(&point))
+    }
+
+    fn frame(&self, caption: String) -> Frame {
+        let cells = self.grid().map(|&dot| match dot {
+            true => Cell::new('#', Colour::YELLOW),
+            false => Cell::new('.', Colour::GREY),
+        });
+        Frame::new(caption, cells)
+    }
+
+    /// Folds the far side of the line at `pos` over onto the near side, which
+    /// leaves the paper `pos` wide (or high). Dots on the line, or too far past
+    /// it to land on the paper, are lost.
+    fn make_fold(&mut self, dir: Direction, pos: usize) {
+        let fold = |n: usize| match n.cmp(&pos) {
+            Ordering::Less => Some(n),
+            Ordering::Equal => None,
+            Ordering::Greater => (2 * pos).checked_sub(n),
+        };
+        self.dots = self
+            .dots
+            .iter()
+            .filter_map(|&Point { x, y }| match dir {
+                Direction::X => Some(Point { x: fold(x)?, y }),
+                Direction::Y => Some(Point { x, y: fold(y)? }),
+            })
+            .collect();
+        match dir {
+            Direction::X => self.width = pos,
+            Direction::Y => self.height = pos,
+        }
+    }
+}
+
+impl Display for DottedPaper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.grid().map(|&dot| if dot { '#' } else { '.' }).fmt(f)
+    }
+}
+
+/// The paper is drawn as big as the furthest dot or fold, so this keeps that to
+/// a sensible size.
+const MAX_COORDINATE: usize = 9999;
+
+#[derive(Copy, Clone)]
+enum Direction {
+    X,
+    Y,
+}
+
+/// `scale` dots on paper the size of the real puzzle's, which folds in half
+/// five times across and seven times down to leave eight capital letters'
+/// worth of space.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let (folded_width, folded_height) = (40, 6);
+    let (x_folds, y_folds) = (5, 7);
+    // Each fold doubles the paper plus the line folded along.
+    let width = (folded_width + 1) * (1 << x_folds) - 1;
+    let height = (folded_height + 1) * (1 << y_folds) - 1;
+
+    let mut input = String::new();
+    for _ in 0..scale {
+        let (x, y) = (rng.below(width), rng.below(height));
+        input.push_str(&format!("{},{}\n", x, y));
+    }
+    input.push('\n');
+    let (mut across, mut down) = (width, height);
+    while across > folded_width || down > folded_height {
+        if down == folded_height || (across > folded_width && rng.chance(0.5)) {
+            across /= 2;
+            input.push_str(&format!("fold along x={}\n", across));
+        } else {
+            down /= 2;
+            input.push_str(&format!("fold along y={}\n", down));
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+#[test]
+fn example() {
+    let (dots, paper) = solve::<Day13>(EXAMPLE);
+    assert_eq!(17, dots);
+    assert_eq!(
+        "#####\n#...#\n#...#\n#...#\n#####\n.....\n.....",
+        paper.to_string()
+    );
+}
+
+#[test]
+fn rejects_folds_past_the_largest_paper() {
+    let input = EXAMPLE.replace("fold along y=7", "fold along y=100000000000");
+    let error = Day13::parse(&input).err().unwrap();
+    assert_eq!("fold positions only go up to 9999", error.message.as_str());
+}
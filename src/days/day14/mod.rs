@@ -0,0 +1,190 @@
+use crate::automaton;
+use crate::error::ParseError;
+use crate::parse::{self, check, map, pair, word};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed = Polymer;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Polymer, ParseError> {
+        Polymer::from_input(input)
+    }
+
+    fn part1(polymer: &Polymer) -> usize {
+        polymer.clone().perform_steps(10)
+    }
+
+    fn part2(polymer: &Polymer) -> usize {
+        polymer.clone().perform_steps(40)
+    }
+}
+
+#[derive(Clone)]
+pub struct Polymer {
+    state: HashMap<(char, char), usize>,
+    template: HashMap<(char, char), char>,
+    first: char,
+    last: char,
+}
+impl Polymer {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let blocks = parse::blocks(input);
+        let (initial_polymer, rules) = blocks
+            .split_first()
+            .ok_or_else(|| ParseError::unexpected_end(input, "expected a polymer template"))?;
+        let initial_polymer = initial_polymer.parse_line(word("a polymer template"))?;
+        let mut state = HashMap::new();
+        initial_polymer
+            .chars()
+            .tuple_windows()
+            .for_each(|(a, b)| *state.entry((a, b)).or_default() += 1);
+
+        let elements = |count: usize, what: &'static str| {
+            check(word(what), move |elements: &&str| {
+                if elements.chars().count() != count {
+                    return Err(format!("expected {}", what));
+                }
+                Ok(())
+            })
+        };
+        let from = elements(2, "a pair of elements");
+        let to = elements(1, "a single element");
+        let rule = map(pair(from, " -> ", to), |(from, to)| {
+            let from: (char, char) = from.chars().collect_tuple().unwrap();
+            (from, to.chars().next().unwrap())
+        });
+        let mut lines = vec![];
+        for block in rules {
+            lines.extend(block.lines().iter().zip(block.parse_lines(&rule)?));
+        }
+        let template = lines
+            .iter()
+            .map(|&(_, rule)| rule)
+            .collect::<HashMap<_, _>>();
+
+        // Every pair there'll ever be is in the polymer to start with, or made by
+        // a rule, so each of those needs a rule of its own.
+        let missing = |pair: &(char, char)| {
+            (!template.contains_key(pair))
+                .then(|| format!("expected a rule for `{}{}`", pair.0, pair.1))
+        };
+        if let Some(message) = initial_polymer
+            .chars()
+            .tuple_windows()
+            .find_map(|pair| missing(&pair))
+        {
+            let (lineno, text) = blocks[0].lines()[0];
+            return Err(ParseError::new(lineno, text, text, message));
+        }
+        for &(&(lineno, text), ((a, b), to_insert)) in &lines {
+            if let Some(message) = missing(&(a, to_insert)).or_else(|| missing(&(to_insert, b))) {
+                return Err(ParseError::new(lineno, text, text, message));
+            }
+        }
+        Ok(Polymer {
+            state,
+            template,
+            first: initial_polymer.chars().next().unwrap(),
+            last: initial_polymer.chars().last().unwrap(),
+        })
+    }
+
+    fn perform_step(&mut self) {
+        let mut new_state: HashMap<(char, char), usize> = HashMap::new();
+        for (&(a, b), &count) in self.state.iter() {
+            let to_insert = self.template[&(a, b)];
+            *new_state.entry((a, to_insert)).or_default() += count;
+            *new_state.entry((to_insert, b)).or_default() += count;
+        }
+        self.state = new_state;
+    }
+
+    fn perform_steps(&mut self, step_count: usize) -> usize {
+        automaton::run(self, step_count, Polymer::perform_step);
+        let counts = self.count_elements();
+        counts.values().max().unwrap() - counts.values().min().unwrap()
+    }
+
+    fn count_elements(&self) -> HashMap<char, usize> {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        self.state.iter().for_each(|((a, b), count)| {
+            *counts.entry(*a).or_default() += count;
+            *counts.entry(*b).or_default() += count;
+        });
+        *counts.entry(self.first).or_default() += 1;
+        *counts.entry(self.last).or_default() += 1;
+        counts
+            .into_iter()
+            .map(|(c, count)| (c, count / 2))
+            .collect()
+    }
+}
+
+/// A template `scale` elements long, and a rule for every pair of the ten
+/// elements it's made of.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let elements = "BCFHKNOPSV".chars().collect_vec();
+    let template = (0..scale.max(1))
+        .map(|_| *rng.pick(&elements))
+        .collect::<String>();
+    let mut input = format!("{}\n\n", template);
+    for &a in &elements {
+        for &b in &elements {
+            input.push_str(&format!("{}{} -> {}\n", a, b, rng.pick(&elements)));
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+#[test]
+fn example() {
+    assert_eq!((1588, 2188189693529), solve::<Day14>(EXAMPLE));
+}
+
+#[test]
+fn rejects_pairs_with_no_rule() {
+    let error = Day14::parse(&EXAMPLE.replace("NNCB", "NNCBX"))
+        .err()
+        .unwrap();
+    assert_eq!(
+        (1, "expected a rule for `BX`"),
+        (error.line, error.message.as_str())
+    );
+    let error = Day14::parse(&EXAMPLE.replace("CH -> B", "CH -> X"))
+        .err()
+        .unwrap();
+    assert_eq!(
+        (3, "expected a rule for `CX`"),
+        (error.line, error.message.as_str())
+    );
+}
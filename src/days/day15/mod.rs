@@ -0,0 +1,144 @@
+use crate::animate::{Cell, Frame};
+use crate::colour::Colour;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::image::{Image, Palette};
+use crate::random::Rng;
+use crate::search;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use rusttype::Point;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse(input, "a risk level from 1 to 9", |c| {
+            c.to_digit(10).filter(|&d| d > 0).map(|d| d as u8)
+        })
+    }
+
+    fn part1(map: &Grid<u8>) -> usize {
+        lowest_total_risk(map)
+    }
+
+    fn part2(map: &Grid<u8>) -> usize {
+        lowest_total_risk(&make_big_map(map))
+    }
+}
+
+fn lowest_total_risk(map: &Grid<u8>) -> usize {
+    lowest_risk_path(map, |_| ()).cost
+}
+
+/// The safest path from the top left to the bottom right, calling `explore`
+/// with each position as the search moves on from it.
+fn lowest_risk_path(
+    map: &Grid<u8>,
+    mut explore: impl FnMut(Point<usize>),
+) -> search::Path<Point<usize>> {
+    let goal = Point {
+        x: map.width() - 1,
+        y: map.height() - 1,
+    };
+    search::astar(
+        Point { x: 0, y: 0 },
+        |&pos| {
+            explore(pos);
+            map.neighbours4(pos).map(|n| (n, map[n] as usize))
+        },
+        |&pos| search::manhattan(pos, goal),
+        |&pos| pos == goal,
+    )
+    .expect("every cell is reachable")
+}
+
+/// Searches the small map, with a frame each time another row's worth of
+/// positions has been explored, and a last one showing the path found.
+pub fn animate(input: &str, show: &mut dyn FnMut(Frame)) -> Result<(), ParseError> {
+    let map = Day15::parse(input)?;
+    let mut explored = map.map(|_| false);
+    let mut explored_count = 0;
+    let path = lowest_risk_path(&map, |pos| {
+        explored[pos] = true;
+        explored_count += 1;
+        if explored_count % map.width() == 0 {
+            let caption = format!("{} positions explored", explored_count);
+            show(frame(&map, &explored, &[], caption));
+        }
+    });
+    let caption = format!("lowest total risk: {}", path.cost);
+    show(frame(&map, &explored, &path.nodes, caption));
+    Ok(())
+}
+
+/// Explored positions are coloured by their risk, and the path picked out on top.
+fn frame(map: &Grid<u8>, explored: &Grid<bool>, path: &[Point<usize>], caption: String) -> Frame {
+    let mut cells = map.map(|&risk| Cell::new(char::from(b'0' + risk), Colour::GREY));
+    for (pos, &risk) in map.iter() {
+        if explored[pos] {
+            cells[pos].colour = risk_colour(risk);
+        }
+    }
+    for &pos in path {
+        cells[pos].colour = Colour::WHITE;
+    }
+    Frame::new(caption, cells)
+}
+
+/// The safest path through the full map, drawn over its risk levels.
+pub fn picture(input: &str) -> Result<Image, ParseError> {
+    let map = make_big_map(&Day15::parse(input)?);
+    let path = lowest_risk_path(&map, |_| ());
+    let mut pixels = map.map(|&risk| risk_colour(risk).towards(Colour::BLACK, 0.5));
+    for &pos in &path.nodes {
+        pixels[pos] = Colour::WHITE;
+    }
+    Ok(Image::new(pixels))
+}
+
+fn risk_colour(risk: u8) -> Colour {
+    Palette::COOL_TO_WARM.at((risk - 1) as f64 / 8.0)
+}
+
+fn make_big_map(base_tile: &Grid<u8>) -> Grid<u8> {
+    let (tile_width, tile_height) = (base_tile.width(), base_tile.height());
+
+    Grid::from_fn(tile_width * 5, tile_height * 5, |pos| {
+        let source = Point {
+            x: pos.x % tile_width,
+            y: pos.y % tile_height,
+        };
+        let inc_x = (pos.x / tile_width) as u8;
+        let inc_y = (pos.y / tile_height) as u8;
+        ((base_tile[source] - 1 + inc_x + inc_y) % 9) + 1
+    })
+}
+
+/// A `scale` by `scale` map of risk levels.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    Grid::from_fn(scale, scale, |_| rng.range(1..10)).to_string() + "\n"
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+#[test]
+fn example() {
+    assert_eq!((40, 315), solve::<Day15>(EXAMPLE));
+}
@@ -0,0 +1,423 @@
+use crate::answers::Answers;
+use crate::days::day16::PacketKind::{Literal, Operator};
+use crate::error::ParseError;
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::{Answer, Solution};
+use itertools::Itertools;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed = Packet;
+    type Answer1 = usize;
+    type Answer2 = Answer<usize>;
+
+    fn parse(input: &str) -> Result<Packet, ParseError> {
+        PacketBuilder::from_hex(input)?.get_next_packet()
+    }
+
+    fn part1(packet: &Packet) -> usize {
+        packet.total_version()
+    }
+
+    fn part2(packet: &Packet) -> Answer<usize> {
+        Answer::found_or(packet.perform_calculation(), TOO_BIG)
+    }
+}
+
+const TOO_BIG: &str = "the transmission evaluates to more than 64 bits hold";
+
+pub struct Packet {
+    version: usize,
+    kind: PacketKind,
+    value: usize,
+    sub_packets: Vec<Packet>,
+    total_bits: usize,
+}
+
+enum PacketKind {
+    Literal,
+    Operator,
+}
+
+impl Packet {
+    pub fn total_version(&self) -> usize {
+        self.version
+            + self
+                .sub_packets
+                .iter()
+                .map(|p| p.total_version())
+                .sum::<usize>()
+    }
+
+    /// What the packet evaluates to, or `None` if a sum or product along the
+    /// way overflows.
+    pub fn perform_calculation(&self) -> Option<usize> {
+        match self.kind {
+            Literal => Some(self.value),
+            Operator => {
+                let sub_results = self
+                    .sub_packets
+                    .iter()
+                    .map(|p| p.perform_calculation())
+                    .collect::<Option<Vec<_>>>()?;
+                let mut sub_results = sub_results.into_iter();
+                Some(match self.value {
+                    0 => sub_results.try_fold(0, usize::checked_add)?,
+                    1 => sub_results.try_fold(1, usize::checked_mul)?,
+                    2 => sub_results.min().unwrap(),
+                    3 => sub_results.max().unwrap(),
+                    5 => {
+                        if sub_results.next().unwrap() > sub_results.next().unwrap() {
+                            1
+                        } else {
+                            0
+                        }
+                    }
+                    6 => {
+                        if sub_results.next().unwrap() < sub_results.next().unwrap() {
+                            1
+                        } else {
+                            0
+                        }
+                    }
+                    7 => {
+                        if sub_results.next().unwrap() == sub_results.next().unwrap() {
+                            1
+                        } else {
+                            0
+                        }
+                    }
+                    x => panic!("Unexpected operator value: {}", x),
+                })
+            }
+        }
+    }
+}
+
+struct PacketBuilder {
+    /// The hex being decoded, kept to point at where an error happened.
+    hex: String,
+    bits: std::vec::IntoIter<char>,
+    bits_read: usize,
+    /// How many operators the packet being read is inside.
+    depth: usize,
+}
+
+impl PacketBuilder {
+    /// Decodes the hex up front, so the builder doesn't borrow from its input.
+    pub fn from_hex(hex: impl AsRef<str>) -> Result<Self, ParseError> {
+        let hex = hex.as_ref().lines().next().unwrap_or("").trim_end();
+        if let Some(column) = hex.chars().position(|c| !c.is_ascii_hexdigit()) {
+            return Err(ParseError::at_column(
+                1,
+                hex,
+                column + 1,
+                "expected a hexadecimal digit",
+            ));
+        }
+        let raw_bits = hex
+            .chars()
+            .flat_map(|c| {
+                format!("{:04b}", c.to_digit(16).unwrap())
+                    .chars()
+                    .collect_vec()
+            })
+            .collect_vec();
+        Ok(PacketBuilder {
+            hex: hex.to_string(),
+            bits: raw_bits.into_iter(),
+            bits_read: 0,
+            depth: 0,
+        })
+    }
+
+    pub fn get_next_packet(&mut self) -> Result<Packet, ParseError> {
+        let version = self.parse_as_numeric(3)?;
+        let kind_value = self.parse_as_numeric(3)?;
+
+        let kind;
+        let value;
+        let sub_packets;
+        let total_bits;
+        if kind_value == 4 {
+            kind = Literal;
+            let parse_result = self.parse_literal_value()?;
+            value = parse_result.0;
+            let literal_bits = parse_result.1;
+            sub_packets = vec![];
+            total_bits = literal_bits + LITERAL_PREAMBLE_BITS;
+        } else {
+            kind = Operator;
+            value = kind_value;
+            if self.depth == MAX_DEPTH {
+                return Err(self.error(&format!("packets only nest up to {} deep", MAX_DEPTH)));
+            }
+            self.depth += 1;
+            let preamble_bits = if self.next_bit()? == '0' {
+                sub_packets = self.get_sub_packets_z()?;
+                LT0_PREAMBLE_BITS
+            } else {
+                sub_packets = self.get_sub_packets_o()?;
+                LT1_PREAMBLE_BITS
+            };
+            self.depth -= 1;
+            match (value, sub_packets.len()) {
+                (0..=3, 0) => return Err(self.error("expected at least one sub-packet")),
+                (5..=7, count) if count != 2 => {
+                    return Err(self.error("expected exactly two sub-packets"))
+                }
+                _ => {}
+            }
+            total_bits = sub_packets.iter().map(|p| p.total_bits).sum::<usize>() + preamble_bits;
+        }
+
+        Ok(Packet {
+            version,
+            kind,
+            value,
+            sub_packets,
+            total_bits,
+        })
+    }
+
+    fn get_sub_packets_z(&mut self) -> Result<Vec<Packet>, ParseError> {
+        let packet_bits = self.parse_as_numeric(15)?;
+        let mut sub_packets = vec![];
+        let mut bit_count = 0;
+        while bit_count < packet_bits {
+            let packet = self.get_next_packet()?;
+            bit_count += packet.total_bits;
+            sub_packets.push(packet);
+        }
+        Ok(sub_packets)
+    }
+
+    fn get_sub_packets_o(&mut self) -> Result<Vec<Packet>, ParseError> {
+        let packet_count = self.parse_as_numeric(11)?;
+        (0..packet_count).map(|_| self.get_next_packet()).collect()
+    }
+
+    fn next_bit(&mut self) -> Result<char, ParseError> {
+        let bit = self
+            .bits
+            .next()
+            .ok_or_else(|| self.error("the transmission ended part way through a packet"))?;
+        self.bits_read += 1;
+        Ok(bit)
+    }
+
+    fn parse_as_numeric(&mut self, bit_count: usize) -> Result<usize, ParseError> {
+        let bits = (0..bit_count)
+            .map(|_| self.next_bit())
+            .collect::<Result<String, _>>()?;
+        Ok(usize::from_str_radix(&bits, 2).unwrap())
+    }
+
+    fn parse_literal_value(&mut self) -> Result<(usize, usize), ParseError> {
+        let mut result = vec![];
+        let mut bit_count = 0;
+
+        loop {
+            bit_count += 5;
+            let is_last_chunk = self.next_bit()? == '0';
+            for _ in 0..4 {
+                result.push(self.next_bit()?);
+            }
+            if is_last_chunk {
+                break;
+            }
+        }
+
+        let value = usize::from_str_radix(&result.into_iter().collect::<String>(), 2)
+            .map_err(|_| self.error("literal value is too large"))?;
+        Ok((value, bit_count))
+    }
+
+    /// An error pointing at the hex digit holding the last bit read.
+    fn error(&self, message: &str) -> ParseError {
+        let column = self.bits_read.saturating_sub(1) / 4 + 1;
+        ParseError::at_column(1, &self.hex, column, message)
+    }
+}
+
+/// How deeply packets can nest, which keeps the recursive parser from running
+/// out of stack.
+const MAX_DEPTH: usize = 200;
+
+const LITERAL_PREAMBLE_BITS: usize = 6;
+const LT0_PREAMBLE_BITS: usize = 6 + 1 + 15;
+const LT1_PREAMBLE_BITS: usize = 6 + 1 + 11;
+
+/// A transmission whose outermost packet has operators nested `scale` deep, or
+/// as deep as the parser allows if that's less.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    generate_answered(rng, scale).0
+}
+
+/// The same transmission as [`generate`], with what it evaluates to for part 2.
+pub fn generate_answered(rng: &mut Rng, scale: usize) -> (String, Answers) {
+    let (bits, value) = generate_packet(rng, scale.min(MAX_DEPTH));
+    (
+        to_hex(&bits) + "\n",
+        Answers::new(None, Some(value.to_string())),
+    )
+}
+
+/// Writes bits out as hex digits, padding the last one with zeros.
+fn to_hex(bits: &[bool]) -> String {
+    bits.chunks(4)
+        .map(|nibble| {
+            let digit = (0..4).fold(0, |acc, i| {
+                acc << 1 | nibble.get(i).copied().unwrap_or(false) as u32
+            });
+            char::from_digit(digit, 16).unwrap().to_ascii_uppercase()
+        })
+        .collect()
+}
+
+/// The bits of a packet `depth` operators deep, and what it evaluates to. Only
+/// one sub-packet of each operator goes any deeper, so that the size grows with
+/// the depth rather than exponentially.
+fn generate_packet(rng: &mut Rng, depth: usize) -> (Vec<bool>, u64) {
+    let mut bits = vec![];
+    push_bits(&mut bits, rng.below(8) as u64, 3);
+    if depth == 0 {
+        let digits = rng.range(1..5);
+        let value = rng.below(1 << (4 * digits)) as u64;
+        push_bits(&mut bits, 4, 3);
+        let groups = (0..16)
+            .rev()
+            .find(|&group| value >> (4 * group) != 0)
+            .unwrap_or(0);
+        for group in (0..=groups).rev() {
+            bits.push(group > 0);
+            push_bits(&mut bits, value >> (4 * group) & 0xf, 4);
+        }
+        return (bits, value);
+    }
+
+    let comparison = rng.chance(0.3);
+    let count = if comparison {
+        2
+    } else {
+        rng.range(1..4) as usize
+    };
+    let deeper = rng.below(count);
+    let (sub_packets, values): (Vec<_>, Vec<_>) = (0..count)
+        .map(|i| generate_packet(rng, if i == deeper { depth - 1 } else { 0 }))
+        .unzip();
+    // Keep well clear of overflowing when evaluated.
+    let product = values
+        .iter()
+        .try_fold(1u64, |acc, &v| acc.checked_mul(v).filter(|&p| p < 1 << 40));
+    let (type_id, value) = if comparison {
+        match rng.below(3) {
+            0 => (5, (values[0] > values[1]) as u64),
+            1 => (6, (values[0] < values[1]) as u64),
+            _ => (7, (values[0] == values[1]) as u64),
+        }
+    } else {
+        match (rng.below(4), product) {
+            (1, Some(product)) => (1, product),
+            (2, _) => (2, *values.iter().min().unwrap()),
+            (3, _) => (3, *values.iter().max().unwrap()),
+            _ => (0, values.iter().sum()),
+        }
+    };
+    push_bits(&mut bits, type_id, 3);
+
+    let length = sub_packets.iter().map(Vec::len).sum::<usize>();
+    if length < 1 << 15 && rng.chance(0.5) {
+        bits.push(false);
+        push_bits(&mut bits, length as u64, 15);
+    } else {
+        bits.push(true);
+        push_bits(&mut bits, count as u64, 11);
+    }
+    bits.extend(sub_packets.into_iter().flatten());
+    (bits, value)
+}
+
+/// Appends the lowest `count` bits of `value`, most significant first.
+fn push_bits(bits: &mut Vec<bool>, value: u64, count: usize) {
+    bits.extend((0..count).rev().map(|i| value >> i & 1 == 1));
+}
+
+#[test]
+fn version_sums() {
+    for (hex, sum) in [
+        ("8A004A801A8002F478", 16),
+        ("620080001611562C8802118E34", 12),
+        ("C0015000016115A2E0802F182340", 23),
+        ("A0016C880162017C3686B18A3D4780", 31),
+    ] {
+        assert_eq!(sum, Day16::part1(&Day16::parse(hex).unwrap()), "{}", hex);
+    }
+}
+
+#[test]
+fn generated_packets_evaluate_as_built() {
+    let mut rng = Rng::new(16);
+    for depth in 0..30 {
+        let (bits, value) = generate_packet(&mut rng, depth);
+        let hex = to_hex(&bits);
+        assert_eq!(
+            Answer::Found(value as usize),
+            Day16::part2(&Day16::parse(&hex).unwrap()),
+            "{}",
+            hex
+        );
+    }
+}
+
+#[test]
+fn nesting_limit() {
+    let hex = generate(&mut Rng::new(1), MAX_DEPTH);
+    assert!(Day16::parse(&hex).is_ok());
+    // One more operator around the outside.
+    let (mut bits, _) = generate_packet(&mut Rng::new(1), MAX_DEPTH);
+    let mut outer = vec![];
+    push_bits(&mut outer, 0, 3);
+    push_bits(&mut outer, 0, 3);
+    outer.push(true);
+    push_bits(&mut outer, 1, 11);
+    outer.append(&mut bits);
+    match Day16::parse(&to_hex(&outer)) {
+        Err(e) => assert!(e.to_string().contains("only nest up to"), "{}", e),
+        Ok(_) => panic!("parsed packets nested {} deep", MAX_DEPTH + 1),
+    }
+}
+
+#[test]
+fn evaluation() {
+    for (hex, value) in [
+        ("C200B40A82", 3),
+        ("04005AC33890", 54),
+        ("880086C3E88112", 7),
+        ("CE00C43D881120", 9),
+        ("D8005AC2A8F0", 1),
+        ("F600BC2D8F", 0),
+        ("9C005AC2F8F0", 0),
+        ("9C0141080250320F1802104A08", 1),
+    ] {
+        assert_eq!(
+            Answer::Found(value),
+            Day16::part2(&Day16::parse(hex).unwrap()),
+            "{}",
+            hex
+        );
+    }
+}
+
+#[test]
+fn no_answer_once_evaluation_overflows() {
+    // The sum of two literals that each fill 64 bits.
+    let hex = "020084FFFFFFFFFFFFFFFFFFEF13FFFFFFFFFFFFFFFFFFBC";
+    assert_eq!(
+        (0, Answer::Impossible(TOO_BIG.to_string())),
+        solve::<Day16>(hex)
+    );
+}
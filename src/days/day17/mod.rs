@@ -0,0 +1,192 @@
+use crate::error::ParseError;
+use crate::parse::{self, check, integer, pair, preceded};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use itertools::Itertools;
+use std::cmp::max;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed = TargetArea;
+    type Answer1 = isize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<TargetArea, ParseError> {
+        TargetArea::from_input(input)
+    }
+
+    fn part1(target: &TargetArea) -> isize {
+        let initial_velocity = target.ymin.abs() - 1;
+        initial_velocity * (initial_velocity + 1) / 2
+    }
+
+    fn part2(target: &TargetArea) -> usize {
+        let mut x_initial_vs: BTreeMap<usize, Vec<isize>> = BTreeMap::new();
+        let mut y_initial_vs: BTreeMap<usize, Vec<isize>> = BTreeMap::new();
+
+        for initial_vx in 0..=target.xmax {
+            for step_number in steps_with_initial_vx(target, initial_vx) {
+                x_initial_vs
+                    .entry(step_number)
+                    .or_default()
+                    .push(initial_vx);
+            }
+        }
+
+        for initial_vy in -target.ymin.abs()..target.ymin.abs() {
+            for step_number in steps_with_initial_vy(target, initial_vy) {
+                y_initial_vs
+                    .entry(step_number)
+                    .or_default()
+                    .push(initial_vy);
+            }
+        }
+
+        let all_initial_vs = x_initial_vs
+            .into_iter()
+            .filter_map(|(step_count, vxs)| {
+                y_initial_vs.get(&step_count).map(move |vys| {
+                    vxs.iter()
+                        .flat_map(|&vx| vys.iter().map(move |&vy| (vx, vy)))
+                        .collect_vec()
+                })
+            })
+            .flatten()
+            .collect::<BTreeSet<(isize, isize)>>();
+
+        all_initial_vs.len()
+    }
+}
+
+fn steps_with_initial_vx(target: &TargetArea, initial_v: isize) -> Vec<usize> {
+    let mut v = initial_v;
+    let mut x = 0;
+    let mut steps = 0;
+
+    let mut result = vec![];
+    while x <= target.xmax && (v > 0 || (x >= target.xmin && steps <= target.max_steps())) {
+        if x >= target.xmin {
+            result.push(steps);
+        }
+        steps += 1;
+        x += v;
+        v = max(v - 1, 0);
+    }
+
+    result
+}
+
+fn steps_with_initial_vy(target: &TargetArea, initial_v: isize) -> Vec<usize> {
+    let mut v = initial_v;
+    let mut y = 0;
+    let mut steps = 0;
+
+    let mut result = vec![];
+    while y >= target.ymin {
+        if y <= target.ymax {
+            result.push(steps);
+        }
+        steps += 1;
+        y += v;
+        v -= 1;
+    }
+
+    result
+}
+
+#[derive(Debug)]
+pub struct TargetArea {
+    xmin: isize,
+    xmax: isize,
+    ymin: isize,
+    ymax: isize,
+}
+
+impl TargetArea {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let line = input.lines().next().unwrap_or("").trim_end();
+        let coordinate = || {
+            check(integer("a coordinate"), |&coordinate: &isize| {
+                if coordinate.unsigned_abs() > MAX_COORDINATE as usize {
+                    return Err(format!(
+                        "coordinates only go from -{0} to {0}",
+                        MAX_COORDINATE
+                    ));
+                }
+                Ok(())
+            })
+        };
+        let range = |prefix| {
+            let bounds = pair(coordinate(), "..", coordinate());
+            check(preceded(prefix, bounds), |&(min, max): &(isize, isize)| {
+                if min > max {
+                    return Err("expected the smaller bound first".to_string());
+                }
+                Ok(())
+            })
+        };
+        let xrange = check(range("x="), |&(xmin, _)| {
+            if xmin < 0 {
+                return Err("expected the target to be ahead of the probe".to_string());
+            }
+            Ok(())
+        });
+        let yrange = check(range("y="), |&(_, ymax)| {
+            if ymax >= 0 {
+                return Err("expected the target to be below the probe".to_string());
+            }
+            Ok(())
+        });
+        let ranges = preceded("target area: ", pair(xrange, ", ", yrange));
+        let ((xmin, xmax), (ymin, ymax)) = parse::line(1, line, ranges)?;
+
+        Ok(TargetArea {
+            xmin,
+            xmax,
+            ymin,
+            ymax,
+        })
+    }
+
+    fn max_steps(&self) -> usize {
+        (self.ymin.abs() * 2) as usize
+    }
+}
+
+/// Part 2 tries every velocity that could reach the target, which takes time
+/// growing with the square of how far off it is, so this keeps that sensible.
+const MAX_COORDINATE: isize = 9999;
+
+/// A target area about `scale` times as far off and as big as the real one, up
+/// to 40 times, past which it wouldn't fit inside the coordinates allowed.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let scale = scale.clamp(1, 40) as i64;
+    let xmin = rng.range(100 * scale..200 * scale);
+    let xmax = xmin + rng.range(20 * scale..40 * scale);
+    let ymin = -rng.range(50 * scale..150 * scale);
+    let ymax = (ymin + rng.range(10 * scale..30 * scale)).min(-1);
+    format!("target area: x={}..{}, y={}..{}\n", xmin, xmax, ymin, ymax)
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "target area: x=20..30, y=-10..-5";
+
+#[test]
+fn example() {
+    assert_eq!((45, 112), solve::<Day17>(EXAMPLE));
+}
+
+#[test]
+fn rejects_coordinates_too_far_out() {
+    let error = Day17::parse("target area: x=20..30, y=-9999999999..-5").unwrap_err();
+    assert_eq!(
+        (26, "coordinates only go from -9999 to 9999"),
+        (error.column, error.message.as_str())
+    );
+    assert!(Day17::parse("target area: x=20..10000, y=-10..-5").is_err());
+    assert!(Day17::parse(&generate(&mut Rng::new(17), 1000)).is_ok());
+}
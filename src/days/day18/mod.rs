@@ -0,0 +1,753 @@
+use crate::days::day18::ChildSide::{Left, Right};
+use crate::error::{parse_lines, ParseError};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+#[cfg(test)]
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, DerefMut};
+use std::str::Chars;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed = Vec<SnailNumber>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<SnailNumber>, ParseError> {
+        if input.trim().is_empty() {
+            return Err(ParseError::unexpected_end(
+                input,
+                "expected a snailfish number",
+            ));
+        }
+        parse_lines(input, SnailNumber::from_line)
+    }
+
+    fn part1(numbers: &Vec<SnailNumber>) -> usize {
+        numbers.iter().cloned().sum::<SnailNumber>().magnitude()
+    }
+
+    fn part2(numbers: &Vec<SnailNumber>) -> usize {
+        numbers
+            .iter()
+            .enumerate()
+            .flat_map(|(i, x)| {
+                numbers.iter().enumerate().map(move |(j, y)| {
+                    if i == j {
+                        0
+                    } else {
+                        (x.clone() + y.clone()).magnitude()
+                    }
+                })
+            })
+            .max()
+            .unwrap()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SnailNumber {
+    value: Box<SnailNode>,
+}
+
+impl SnailNumber {
+    pub fn from_line(lineno: usize, line: &str) -> Result<Self, ParseError> {
+        let mut chars = line.chars();
+        let value = SnailNode::from_chars(lineno, line, &mut chars, None)?;
+        if !chars.as_str().is_empty() {
+            return Err(ParseError::new(
+                lineno,
+                line,
+                chars.as_str(),
+                "unexpected characters after the number",
+            ));
+        }
+        Ok(SnailNumber { value })
+    }
+
+    pub fn magnitude(&self) -> usize {
+        self.value.magnitude()
+    }
+
+    #[cfg(test)]
+    fn iter(&self) -> SnailIter<'_> {
+        SnailIter {
+            next_node: Some(&self.value),
+        }
+    }
+
+    #[cfg(test)]
+    fn iter_mut(&mut self) -> SnailIterMut<'_> {
+        self.value.iter_mut()
+    }
+
+    fn reduce(&mut self) {
+        let mut made_change = true;
+        while made_change {
+            made_change = self.check_for_explosions();
+            if !made_change {
+                made_change = self.check_for_splits();
+            }
+        }
+    }
+
+    fn check_for_explosions(&mut self) -> bool {
+        let mut made_change = false;
+        let mut current_node = self.value.as_mut();
+        while let Some(node) = current_node.next() {
+            current_node = node;
+            if current_node.value().is_none() && current_node.contents.depth >= 4 {
+                current_node.explode();
+                made_change = true;
+                break;
+            }
+        }
+        made_change
+    }
+
+    fn check_for_splits(&mut self) -> bool {
+        let mut made_change = false;
+        let mut current_node = self.value.as_mut();
+        while let Some(node) = current_node.next() {
+            current_node = node;
+            match current_node.value() {
+                Some(x) if x >= 10 => {
+                    current_node.split();
+                    made_change = true;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        made_change
+    }
+
+    /// The pair of `self` and `rhs`, before it's reduced.
+    fn join(self, rhs: Self) -> Self {
+        let mut result_node = SnailNode::new(None);
+        let as_ptr: *mut SnailNode = &mut *result_node;
+        let mut left = self.value;
+        let mut right = rhs.value;
+        left.links.parent = Some(Parent {
+            node: as_ptr,
+            side: Left,
+        });
+        right.links.parent = Some(Parent {
+            node: as_ptr,
+            side: Right,
+        });
+        left.iter_mut().for_each(|contents| contents.depth += 1);
+        right.iter_mut().for_each(|contents| contents.depth += 1);
+
+        result_node.links.children = Some(SnailPair { left, right });
+        SnailNumber { value: result_node }
+    }
+}
+
+impl Add for SnailNumber {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self.join(rhs);
+        result.reduce();
+        result
+    }
+}
+
+impl Clone for SnailNumber {
+    fn clone(&self) -> Self {
+        SnailNumber {
+            value: self.value.clone_with_parent(None),
+        }
+    }
+}
+
+impl Display for SnailNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// Panics on an empty iterator, since there's no snailfish number that adds nothing.
+impl Sum for SnailNumber {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, num| acc + num).unwrap()
+    }
+}
+
+#[derive(Debug)]
+struct SnailNode {
+    links: SnailLinks,
+    contents: SnailContents,
+}
+
+impl SnailNode {
+    fn new(parent: Option<Parent>) -> Box<Self> {
+        let depth = match &parent {
+            Some(p) => unsafe { p.node.as_ref().unwrap().depth() + 1 },
+            _ => 0,
+        };
+        Box::new(Self {
+            links: SnailLinks {
+                parent,
+                children: None,
+            },
+            contents: SnailContents { value: None, depth },
+        })
+    }
+
+    /// Reads a node from `chars`, which is what's left of `line`.
+    fn from_chars(
+        lineno: usize,
+        line: &str,
+        chars: &mut Chars,
+        parent: Option<Parent>,
+    ) -> Result<Box<Self>, ParseError> {
+        let mut this = Self::new(parent);
+        let as_ptr: *mut Self = &mut *this;
+        match chars.next() {
+            Some(c) if let Some(x) = c.to_digit(10) => this.contents.value = Some(x as u8),
+            Some('[') if this.depth() > 3 => {
+                return Err(char_error(
+                    lineno,
+                    line,
+                    chars,
+                    Some('['),
+                    "pair nested inside four pairs, which a reduced number can't have",
+                ))
+            }
+            Some('[') => {
+                let left = Self::from_chars(
+                    lineno,
+                    line,
+                    chars,
+                    Some(Parent {
+                        node: as_ptr,
+                        side: Left,
+                    }),
+                )?;
+                expect_char(lineno, line, chars, ',')?;
+                let right = Self::from_chars(
+                    lineno,
+                    line,
+                    chars,
+                    Some(Parent {
+                        node: as_ptr,
+                        side: Right,
+                    }),
+                )?;
+                expect_char(lineno, line, chars, ']')?;
+                this.links.children = Some(SnailPair { left, right });
+            }
+            found => {
+                return Err(char_error(
+                    lineno,
+                    line,
+                    chars,
+                    found,
+                    "expected a digit or `[`",
+                ))
+            }
+        };
+        Ok(this)
+    }
+
+    /// A deep copy whose children point back at the copy rather than at `self`.
+    fn clone_with_parent(&self, parent: Option<Parent>) -> Box<Self> {
+        let mut this = Box::new(Self {
+            links: SnailLinks {
+                parent,
+                children: None,
+            },
+            contents: self.contents.clone(),
+        });
+        let as_ptr: *mut Self = &mut *this;
+        if let Some(children) = &self.links.children {
+            this.links.children = Some(SnailPair {
+                left: children.left.clone_with_parent(Some(Parent {
+                    node: as_ptr,
+                    side: Left,
+                })),
+                right: children.right.clone_with_parent(Some(Parent {
+                    node: as_ptr,
+                    side: Right,
+                })),
+            });
+        }
+        this
+    }
+
+    fn value(&self) -> Option<u8> {
+        self.contents.value
+    }
+
+    fn depth(&self) -> u8 {
+        self.contents.depth
+    }
+
+    fn parent(&self) -> Option<*mut Self> {
+        self.links.parent.as_ref().map(|p| p.node)
+    }
+
+    #[cfg(test)]
+    fn sibling(&self) -> Option<&Self> {
+        let parent = self.links.parent.as_ref();
+        unsafe {
+            parent.and_then(|p| match p.side {
+                Left => (*p.node).child(Right),
+                Right => (*p.node).child(Left),
+            })
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    fn sibling_mut(&self) -> Option<&mut Self> {
+        let parent = self.links.parent.as_ref();
+        unsafe {
+            parent.and_then(|p| match p.side {
+                Left => (*p.node).child_mut(Right),
+                Right => (*p.node).child_mut(Left),
+            })
+        }
+    }
+
+    fn side(&self) -> Option<ChildSide> {
+        self.links.parent.as_ref().map(|p| p.side)
+    }
+
+    fn child_mut(&mut self, side: ChildSide) -> Option<&mut Self> {
+        self.links.children.as_mut().map(|kids| match side {
+            Left => kids.left.deref_mut(),
+            Right => kids.right.deref_mut(),
+        })
+    }
+
+    fn child(&self, side: ChildSide) -> Option<&Self> {
+        self.links.children.as_ref().map(|kids| match side {
+            Left => kids.left.as_ref(),
+            Right => kids.right.as_ref(),
+        })
+    }
+
+    fn explode(&mut self) {
+        if let Some(n) = self.neighbour(Left) {
+            *n.contents.value.as_mut().unwrap() += self.child(Left).unwrap().value().unwrap()
+        }
+        if let Some(n) = self.neighbour(Right) {
+            *n.contents.value.as_mut().unwrap() += self.child(Right).unwrap().value().unwrap()
+        }
+        self.links.children = None;
+        self.contents.value = Some(0u8);
+    }
+
+    fn split(&mut self) {
+        let value = self.value().unwrap();
+        let left_value = value / 2;
+        let mut left = Self::new(Some(Parent {
+            node: self as *mut Self,
+            side: Left,
+        }));
+        left.contents.value = Some(left_value);
+        let right_value = value / 2 + value % 2;
+        let mut right = Self::new(Some(Parent {
+            node: self as *mut Self,
+            side: Right,
+        }));
+        right.contents.value = Some(right_value);
+        self.contents.value = None;
+        self.links.children = Some(SnailPair { left, right });
+    }
+
+    fn bottom_child(&mut self, direction: ChildSide) -> &mut Self {
+        let mut node = self;
+        while node.child(direction).is_some() {
+            node = node.child_mut(direction).unwrap();
+        }
+        node
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    fn neighbour(&self, side: ChildSide) -> Option<&mut Self> {
+        let mut current_node = self;
+        // Find first left turn.
+        while current_node.side() == Some(side) {
+            current_node = unsafe { &mut *current_node.parent().unwrap() };
+        }
+
+        // Take the left turn, and follow it to the right.
+        current_node
+            .sibling_mut()
+            .map(|n| n.bottom_child(side.reverse()))
+    }
+
+    fn iter_mut(&mut self) -> SnailIterMut<'_> {
+        SnailIterMut {
+            next_node: Some(self),
+        }
+    }
+
+    fn next(&mut self) -> Option<&mut Self> {
+        match (
+            &mut self.links.children,
+            &self.links.parent.as_ref().map(|p| p.side),
+        ) {
+            (Some(SnailPair { left, right: _ }), _) => Some(left),
+            (None, Some(Left)) => unsafe {
+                self.links
+                    .parent
+                    .as_mut()
+                    .and_then(|p| (*p.node).child_mut(Right))
+            },
+            (None, Some(Right)) => {
+                let mut potential_next = unsafe { &mut *self.links.parent.as_mut().unwrap().node };
+                while potential_next.side() == Some(Right) {
+                    potential_next = unsafe { &mut *potential_next.parent().unwrap() }
+                }
+                potential_next.sibling_mut()
+            }
+            _ => panic!("unexpected nothing"),
+        }
+    }
+
+    fn magnitude(&self) -> usize {
+        if let Some(children) = &self.links.children {
+            3 * children.left.magnitude() + 2 * children.right.magnitude()
+        } else {
+            self.value().unwrap() as usize
+        }
+    }
+}
+
+impl Display for SnailNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(children) = &self.links.children {
+            write!(f, "[{},{}]", children.left, children.right)
+        } else {
+            write!(f, "{}", self.contents.value.unwrap())
+        }
+    }
+}
+
+impl PartialEq for SnailNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+            && self.depth() == other.depth()
+            && self.side() == other.side()
+            && self.child(Left) == other.child(Left)
+            && self.child(Right) == other.child(Right)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct SnailContents {
+    value: Option<u8>,
+    depth: u8,
+}
+
+#[derive(Debug, PartialEq)]
+struct SnailLinks {
+    parent: Option<Parent>,
+    children: Option<SnailPair>,
+}
+
+#[derive(Debug, PartialEq)]
+struct SnailPair {
+    left: Box<SnailNode>,
+    right: Box<SnailNode>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Parent {
+    node: *mut SnailNode,
+    side: ChildSide,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ChildSide {
+    Left,
+    Right,
+}
+
+impl ChildSide {
+    fn reverse(&self) -> Self {
+        match self {
+            Left => Right,
+            Right => Left,
+        }
+    }
+}
+
+fn expect_char(
+    lineno: usize,
+    line: &str,
+    chars: &mut Chars,
+    expected: char,
+) -> Result<(), ParseError> {
+    match chars.next() {
+        Some(c) if c == expected => Ok(()),
+        found => Err(char_error(
+            lineno,
+            line,
+            chars,
+            found,
+            &format!("expected `{}`", expected),
+        )),
+    }
+}
+
+/// An error at the character `chars` just yielded, or at the end of the line if it was empty.
+fn char_error(
+    lineno: usize,
+    line: &str,
+    chars: &Chars,
+    found: Option<char>,
+    message: &str,
+) -> ParseError {
+    let column = match found {
+        Some(_) => line[..line.len() - chars.as_str().len()].chars().count(),
+        None => line.chars().count() + 1,
+    };
+    ParseError::at_column(lineno, line, column, message)
+}
+
+struct SnailIterMut<'a> {
+    next_node: Option<&'a mut SnailNode>,
+}
+
+impl<'a> Iterator for SnailIterMut<'a> {
+    type Item = &'a mut SnailContents;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(node) = self.next_node.take() {
+            let to_emit = &mut node.contents;
+            self.next_node = match (
+                &mut node.links.children,
+                &node.links.parent.as_ref().map(|p| p.side),
+            ) {
+                (Some(SnailPair { left, right: _ }), _) => Some(left),
+                (None, Some(Left)) => unsafe {
+                    node.links
+                        .parent
+                        .as_mut()
+                        .and_then(|p| (*p.node).child_mut(Right))
+                },
+                (None, Some(Right)) => {
+                    let mut potential_next =
+                        unsafe { &mut *node.links.parent.as_mut().unwrap().node };
+                    while potential_next.side() == Some(Right) {
+                        potential_next = unsafe { &mut *potential_next.parent().unwrap() }
+                    }
+                    potential_next.sibling_mut()
+                }
+                _ => panic!("unexpected nothing"),
+            };
+            Some(to_emit)
+        } else {
+            None
+        }
+    }
+}
+
+/// `scale` lines of homework, each a reduced snailfish number.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            let mut line = String::new();
+            generate_number(rng, 0, &mut line);
+            line + "\n"
+        })
+        .collect()
+}
+
+/// Writes a random element `depth` pairs down: a pair, which can't be nested
+/// inside four others in a reduced number, or a single digit.
+fn generate_number(rng: &mut Rng, depth: usize, out: &mut String) {
+    if depth == 4 || (depth > 0 && rng.chance(0.4)) {
+        out.push(char::from(b'0' + rng.below(10) as u8));
+    } else {
+        out.push('[');
+        generate_number(rng, depth + 1, out);
+        out.push(',');
+        generate_number(rng, depth + 1, out);
+        out.push(']');
+    }
+}
+
+#[cfg(test)]
+struct SnailIter<'a> {
+    next_node: Option<&'a SnailNode>,
+}
+
+#[cfg(test)]
+impl<'a> Iterator for SnailIter<'a> {
+    type Item = &'a SnailContents;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(node) = self.next_node {
+            let to_emit = &node.contents;
+            self.next_node = match (
+                &node.links.children,
+                &node.links.parent.as_ref().map(|p| p.side),
+            ) {
+                (Some(SnailPair { left, right: _ }), _) => Some(left),
+                (None, Some(Left)) => unsafe {
+                    node.links
+                        .parent
+                        .as_ref()
+                        .and_then(|p| (*p.node).child(Right))
+                },
+                (None, Some(Right)) => {
+                    let mut potential_next =
+                        unsafe { &mut *node.links.parent.as_ref().unwrap().node };
+                    while potential_next.side() == Some(Right) {
+                        potential_next = unsafe { &mut *potential_next.parent().unwrap() }
+                    }
+                    potential_next.sibling()
+                }
+                _ => panic!("unexpected nothing"),
+            };
+            Some(to_emit)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+fn number(s: &str) -> SnailNumber {
+    SnailNumber::from_line(1, s).unwrap()
+}
+
+#[test]
+fn iter_mut() {
+    let mut number = number("[[1,2],3]");
+    number.iter_mut().for_each(|node| node.depth += 1);
+    assert_eq!(
+        vec![1, 2, 3, 3, 2],
+        number.iter_mut().map(|node| node.depth).collect_vec()
+    );
+}
+
+#[test]
+fn explode() {
+    let mut number = number("[[1,2],3]");
+    number.value.child_mut(Left).unwrap().explode();
+    assert_eq!(
+        vec![0u8, 5u8],
+        number
+            .iter_mut()
+            .filter_map(|node| node.value)
+            .collect_vec()
+    )
+}
+
+#[test]
+fn split() {
+    let mut number = number("[[1,2],3]");
+    number.value.child_mut(Right).unwrap().split();
+    assert_eq!(
+        vec![1u8, 2u8, 1u8, 2u8],
+        number
+            .iter_mut()
+            .filter_map(|node| node.value)
+            .collect_vec()
+    )
+}
+
+#[test]
+fn add() {
+    let num1 = number("[[1,2],3]");
+    let num2 = number("[4,[[5,6],7]]");
+    assert_eq!(
+        (num1 + num2).iter().map(|n| n.depth).collect_vec(),
+        number("[[[1,2],3],[4,[[5,6],7]]]")
+            .iter()
+            .map(|n| n.depth)
+            .collect_vec(),
+    )
+}
+
+#[test]
+fn reduce() {
+    let mut num = number("[[[[4,3],4],4],[7,[[8,4],9]]]").join(number("[1,1]"));
+    assert_eq!("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]", num.to_string());
+    num.reduce();
+    assert_eq!(number("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"), num);
+}
+
+#[test]
+fn sum() {
+    let nums = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]"
+        .lines()
+        .map(number);
+    assert_eq!(
+        number("[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]"),
+        nums.sum()
+    );
+}
+
+#[test]
+fn generated_numbers_round_trip() {
+    let homework = generate(&mut Rng::new(18), 50);
+    for (lineno, line) in homework.lines().enumerate() {
+        let number = SnailNumber::from_line(lineno + 1, line).unwrap();
+        assert_eq!(line, number.to_string());
+    }
+}
+
+#[test]
+fn magnitude() {
+    assert_eq!(143, number("[[1,2],[[3,4],5]]").magnitude());
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+#[test]
+fn example() {
+    assert_eq!((4140, 3993), solve::<Day18>(EXAMPLE));
+}
+
+#[test]
+fn clones_are_independent() {
+    let original = number("[[[[4,3],4],4],[7,[[8,4],9]]]");
+    let sum = original.clone() + number("[1,1]");
+    assert_eq!("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", sum.to_string());
+    assert_eq!("[[[[4,3],4],4],[7,[[8,4],9]]]", original.to_string());
+}
+
+#[test]
+fn rejects_empty_input() {
+    assert!(Day18::parse("").is_err());
+}
+
+#[test]
+fn rejects_pairs_nested_inside_four_pairs() {
+    // Adding [1,1] would nest [1,2] five deep, too deep to explode.
+    let error = Day18::parse("[[[[[1,2],3],4],5],6]\n[1,1]\n").unwrap_err();
+    assert_eq!((1, 5), (error.line, error.column));
+}
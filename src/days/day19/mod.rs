@@ -0,0 +1,545 @@
+use crate::days::day19::transform::T;
+use crate::error::ParseError;
+use crate::parse::{self, check, integer, map, pair, preceded, terminated};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::{Answer, Solution};
+#[cfg(test)]
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Sub};
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed = Vec<Scanner>;
+    type Answer1 = Answer<usize>;
+    type Answer2 = Answer<i32>;
+
+    fn parse(input: &str) -> Result<Vec<Scanner>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(scanners: &Vec<Scanner>) -> Answer<usize> {
+        assemble_map(scanners).map(|map| map.beacons.len())
+    }
+
+    fn part2(scanners: &Vec<Scanner>) -> Answer<i32> {
+        assemble_map(scanners).map(|map| {
+            let positions = map.scanners;
+            positions
+                .iter()
+                .flat_map(|a| positions.iter().map(move |b| a.manhattan(b)))
+                .max()
+                .unwrap_or(0)
+        })
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Scanner>, ParseError> {
+    let header = preceded(
+        "--- scanner ",
+        terminated(integer::<usize>("a scanner number"), " ---"),
+    );
+    let coordinate = || {
+        check(integer("a coordinate"), |&c: &i32| {
+            match (-RANGE..=RANGE).contains(&c) {
+                true => Ok(()),
+                false => Err(format!("expected a beacon within {} of the scanner", RANGE)),
+            }
+        })
+    };
+    let beacon = map(
+        pair(coordinate(), ",", pair(coordinate(), ",", coordinate())),
+        |(x, (y, z))| Point { x, y, z },
+    );
+    let scanners = parse::blocks(input)
+        .iter()
+        .map(|block| {
+            let (&(lineno, text), beacons) = block.lines().split_first().unwrap();
+            parse::line(lineno, text, &header)?;
+            if beacons.is_empty() {
+                return Err(block.ended_early("expected a beacon"));
+            }
+            let beacons = beacons
+                .iter()
+                .map(|&(lineno, text)| parse::line(lineno, text, &beacon))
+                .collect::<Result<_, _>>()?;
+            Ok(Scanner::new(beacons))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if scanners.is_empty() {
+        return Err(ParseError::unexpected_end(
+            input,
+            "expected at least one scanner",
+        ));
+    }
+    Ok(scanners)
+}
+
+/// The combined picture once every scanner has been placed relative to the first.
+struct OceanMap {
+    beacons: HashSet<Point>,
+    scanners: Vec<Point>,
+}
+
+/// Places each scanner against the beacons found so far, or says which scanner couldn't be
+/// placed.
+fn assemble_map(scanners: &[Scanner]) -> Answer<OceanMap> {
+    // The first transformation is the identity, so scanner 0 defines the axes.
+    let mut beacons = scanners[0].beacons[0].clone();
+    let mut positions = vec![Point { x: 0, y: 0, z: 0 }];
+    let mut unplaced = scanners.iter().enumerate().skip(1).collect::<Vec<_>>();
+
+    while !unplaced.is_empty() {
+        let unplaced_count = unplaced.len();
+        unplaced.retain(|(_, scanner)| match scanner.locate(&beacons) {
+            Some((position, placed_beacons)) => {
+                beacons.extend(placed_beacons);
+                positions.push(position);
+                false
+            }
+            None => true,
+        });
+        if unplaced.len() == unplaced_count {
+            return Answer::Impossible(format!(
+                "scanner {} shares fewer than {} beacons with the others",
+                unplaced[0].0, MIN_OVERLAP
+            ));
+        }
+    }
+
+    Answer::Found(OceanMap {
+        beacons,
+        scanners: positions,
+    })
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct Point {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+impl Point {
+    fn transformed(&self, transformation: T) -> Point {
+        let mut p = *self;
+        let (c2, c3, c4) = transformation.destruc();
+        for _ in 0..c2 {
+            p = Point {
+                x: -p.y,
+                y: -p.x,
+                z: -p.z,
+            };
+        }
+        for _ in 0..c3 {
+            p = Point {
+                x: p.y,
+                y: p.z,
+                z: p.x,
+            };
+        }
+        for _ in 0..c4 {
+            p = Point {
+                x: p.x,
+                y: p.z,
+                z: -p.y,
+            };
+        }
+        p
+    }
+}
+
+impl Point {
+    fn manhattan(&self, other: &Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("({},{},{})", self.x, self.y, self.z))
+    }
+}
+
+pub struct Scanner {
+    /// The scanner's beacons as seen from each of the 24 possible orientations.
+    beacons: Vec<HashSet<Point>>,
+}
+
+impl Scanner {
+    fn new(raw_beacons: HashSet<Point>) -> Self {
+        let beacons = T::all()
+            .map(|t| raw_beacons.iter().map(|b| b.transformed(t)).collect())
+            .collect();
+        Scanner { beacons }
+    }
+
+    /// Tries to find an orientation and position for this scanner at which at least 12 of its
+    /// beacons line up with `known` ones. Returns the scanner's position and its beacons, both
+    /// in `known`'s frame of reference.
+    fn locate(&self, known: &HashSet<Point>) -> Option<(Point, HashSet<Point>)> {
+        self.beacons.iter().find_map(|oriented| {
+            let mut offsets: HashMap<Point, usize> = HashMap::new();
+            for &a in known {
+                for &b in oriented {
+                    *offsets.entry(a - b).or_default() += 1;
+                }
+            }
+            offsets
+                .into_iter()
+                .find(|&(_, count)| count >= MIN_OVERLAP)
+                .map(|(position, _)| (position, oriented.iter().map(|&b| b + position).collect()))
+        })
+    }
+}
+
+const MIN_OVERLAP: usize = 12;
+
+/// How far away a scanner can see beacons along each axis.
+const RANGE: i32 = 1000;
+
+mod transform {
+    #[derive(Clone, Copy, Debug)]
+    pub struct T(u8, u8, u8);
+
+    impl T {
+        pub fn new(c2: u8, c3: u8, c4: u8) -> Self {
+            if c2 >= 2 || c3 >= 3 || c4 >= 4 {
+                panic!("Order of rotation too large: {}, {}, {}", c2, c3, c4)
+            }
+            T(c2, c3, c4)
+        }
+        pub fn destruc(&self) -> (u8, u8, u8) {
+            (self.0, self.1, self.2)
+        }
+        pub fn all() -> impl Iterator<Item = T> {
+            TIter {
+                c2: 0,
+                c3: 0,
+                c4: 0,
+                first: true,
+            }
+        }
+    }
+
+    struct TIter {
+        c2: u8,
+        c3: u8,
+        c4: u8,
+        first: bool,
+    }
+
+    impl Iterator for TIter {
+        type Item = T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            match (self.c2 < 1, self.c3 < 2, self.c4 < 3, self.first) {
+                (_, _, _, true) => self.first = false,
+                (_, _, true, false) => self.c4 += 1,
+                (_, true, false, false) => {
+                    self.c3 += 1;
+                    self.c4 = 0
+                }
+                (true, false, false, false) => {
+                    self.c2 += 1;
+                    self.c3 = 0;
+                    self.c4 = 0
+                }
+                (false, false, false, false) => return None,
+            }
+            Some(T::new(self.c2, self.c3, self.c4))
+        }
+    }
+}
+
+/// `scale` scanners, each placed so that it shares at least twelve beacons
+/// with one placed before it, and turned to face one of the 24 ways at random.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let within = |a: Point, b: Point, distance: i32| {
+        let d = a - b;
+        d.x.abs() <= distance && d.y.abs() <= distance && d.z.abs() <= distance
+    };
+    let random_near = |rng: &mut Rng, centre: Point, distance: i32| {
+        let mut offset = || rng.range(-distance as i64..distance as i64 + 1) as i32;
+        centre
+            + Point {
+                x: offset(),
+                y: offset(),
+                z: offset(),
+            }
+    };
+
+    let mut scanners = vec![Point { x: 0, y: 0, z: 0 }];
+    let mut beacons: Vec<Point> = vec![];
+    // Beacons in range of all of `near`, staying a little inside the range so
+    // that it's clear-cut which scanners see them.
+    let mut place_beacons = |rng: &mut Rng, count: usize, near: &[Point]| {
+        let mut placed = 0;
+        while placed < count {
+            let beacon = random_near(rng, near[0], RANGE - 10);
+            if near
+                .iter()
+                .all(|&scanner| within(beacon, scanner, RANGE - 10))
+                && !beacons.contains(&beacon)
+            {
+                beacons.push(beacon);
+                placed += 1;
+            }
+        }
+    };
+    place_beacons(rng, 12, &scanners);
+    for _ in 1..scale.max(1) {
+        let neighbour = *rng.pick(&scanners);
+        let scanner = random_near(rng, neighbour, RANGE + 100);
+        place_beacons(rng, 12, &[neighbour, scanner]);
+        place_beacons(rng, 6, &[scanner]);
+        scanners.push(scanner);
+    }
+
+    let orientations = T::all().collect::<Vec<_>>();
+    let mut input = String::new();
+    for (i, &scanner) in scanners.iter().enumerate() {
+        let orientation = *rng.pick(&orientations);
+        let mut seen = beacons
+            .iter()
+            .filter(|&&beacon| within(beacon, scanner, RANGE))
+            .map(|&beacon| (beacon - scanner).transformed(orientation))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut seen);
+        if i > 0 {
+            input.push('\n');
+        }
+        input.push_str(&format!("--- scanner {} ---\n", i));
+        for Point { x, y, z } in seen {
+            input.push_str(&format!("{},{},{}\n", x, y, z));
+        }
+    }
+    input
+}
+
+#[test]
+fn all_transforms() {
+    let test_points = [
+        Point { x: 1, y: 0, z: 0 },
+        Point { x: 0, y: 1, z: 0 },
+        Point { x: 0, y: 0, z: 1 },
+    ];
+    let result = T::all()
+        .map(|t| {
+            let new_points = test_points
+                .iter()
+                .map(|p| p.transformed(t))
+                .collect_tuple::<(Point, Point, Point)>()
+                .unwrap();
+            (new_points.0, new_points.1, new_points.2, t.destruc())
+        })
+        .collect_vec();
+    assert_eq!(
+        24,
+        result
+            .iter()
+            .map(|(p1, p2, p3, _)| (p1, p2, p3))
+            .unique()
+            .count(),
+        "\nexpected 24 distinct tuples, got: {:?}\n",
+        result
+            .iter()
+            .sorted()
+            .map(|ps| format!("({}, {}, {}, {:?})", ps.0, ps.1, ps.2, ps.3))
+            .collect_vec(),
+    );
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14";
+
+#[test]
+fn example() {
+    assert_eq!(
+        (Answer::Found(79), Answer::Found(3621)),
+        solve::<Day19>(EXAMPLE)
+    );
+}
+
+#[test]
+fn no_answer_for_a_scanner_that_overlaps_no_other() {
+    let lone = "\n\n--- scanner 5 ---\n1,2,3\n";
+    let scanners = Day19::parse(&format!("{}{}", EXAMPLE, lone)).unwrap();
+    let why = "scanner 5 shares fewer than 12 beacons with the others";
+    assert_eq!(Answer::Impossible(why.to_string()), Day19::part1(&scanners));
+}
+
+#[test]
+fn rejects_beacons_out_of_range() {
+    let error = Day19::parse("--- scanner 0 ---\n1,-2147483648,3\n")
+        .err()
+        .unwrap();
+    assert_eq!(
+        (2, "expected a beacon within 1000 of the scanner"),
+        (error.line, error.message.as_str())
+    );
+}
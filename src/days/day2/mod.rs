@@ -0,0 +1,136 @@
+use crate::error::ParseError;
+use crate::parse::{self, choice, integer, map, pair};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::{Answer, Solution};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<Instruction>;
+    type Answer1 = Answer<u64>;
+    type Answer2 = Answer<u64>;
+
+    /// Also checks that the submarine never goes up past the surface, which is
+    /// as far as part 1's depth and part 2's aim ever drop.
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        let instruction = pair(choice(DIRECTIONS), " ", integer("a distance"));
+        let instructions = parse::lines(
+            input,
+            map(instruction, |(direction, distance)| direction(distance)),
+        )?;
+        let mut depth = 0;
+        for ((i, line), instruction) in input.lines().enumerate().zip(&instructions) {
+            match *instruction {
+                Instruction::Fwd(_) => {}
+                Instruction::Up(dist) if dist as u64 > depth => {
+                    let message = "expected the submarine to stay below the surface";
+                    return Err(ParseError::new(i + 1, line, line, message));
+                }
+                Instruction::Up(dist) => depth -= dist as u64,
+                Instruction::Down(dist) => depth += dist as u64,
+            }
+        }
+        Ok(instructions)
+    }
+
+    fn part1(instructions: &Vec<Instruction>) -> Answer<u64> {
+        // Sums of u32 distances can't overflow a u64, but their product can.
+        let (mut x, mut depth) = (0, 0);
+        instructions.iter().for_each(|ins| match *ins {
+            Instruction::Fwd(dist) => x += dist as u64,
+            Instruction::Up(dist) => depth -= dist as u64,
+            Instruction::Down(dist) => depth += dist as u64,
+        });
+        Answer::found_or(x.checked_mul(depth), TOO_FAR)
+    }
+
+    fn part2(instructions: &Vec<Instruction>) -> Answer<u64> {
+        let course = || {
+            let (mut x, mut depth, mut aim) = (0u64, 0u64, 0u64);
+            for ins in instructions {
+                match *ins {
+                    Instruction::Fwd(dist) => {
+                        x += dist as u64;
+                        depth = depth.checked_add(aim.checked_mul(dist as u64)?)?;
+                    }
+                    Instruction::Up(dist) => aim -= dist as u64,
+                    Instruction::Down(dist) => aim += dist as u64,
+                }
+            }
+            x.checked_mul(depth)
+        };
+        Answer::found_or(course(), TOO_FAR)
+    }
+}
+
+const TOO_FAR: &str = "the submarine goes too far to multiply its position out";
+
+#[derive(Debug)]
+pub enum Instruction {
+    Fwd(u32),
+    Up(u32),
+    Down(u32),
+}
+
+/// Makes an instruction from its distance.
+type MakeInstruction = fn(u32) -> Instruction;
+
+/// The instructions' words, with what each one makes.
+const DIRECTIONS: &[(&str, MakeInstruction)] = &[
+    ("forward", Instruction::Fwd),
+    ("up", Instruction::Up),
+    ("down", Instruction::Down),
+];
+
+/// `scale` instructions, never rising above the surface.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut depth = 0;
+    (0..scale)
+        .map(|_| {
+            let distance = rng.range(1..10);
+            let direction = match rng.below(3) {
+                0 if depth >= distance => {
+                    depth -= distance;
+                    "up"
+                }
+                1 => {
+                    depth += distance;
+                    "down"
+                }
+                _ => "forward",
+            };
+            format!("{} {}\n", direction, distance)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+#[test]
+fn example() {
+    assert_eq!(
+        (Answer::Found(150), Answer::Found(900)),
+        solve::<Day2>(EXAMPLE)
+    );
+}
+
+#[test]
+fn stays_below_the_surface() {
+    let error = Day2::parse("down 2\nup 5\nforward 3").unwrap_err();
+    assert_eq!(
+        (2, "expected the submarine to stay below the surface"),
+        (error.line, error.message.as_str())
+    );
+    let far = Day2::parse("down 4000000000\nforward 4000000000\nforward 4000000000").unwrap();
+    assert_eq!(Answer::Impossible(TOO_FAR.to_string()), Day2::part1(&far));
+    assert_eq!(Answer::Impossible(TOO_FAR.to_string()), Day2::part2(&far));
+}
@@ -0,0 +1,208 @@
+use crate::automaton;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::parse::{self, check, Failure, Parsed};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use rusttype::Point;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed = TrenchMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<TrenchMap, ParseError> {
+        TrenchMap::from_input(input)
+    }
+
+    fn part1(map: &TrenchMap) -> usize {
+        map.enhanced(2).lit_count()
+    }
+
+    fn part2(map: &TrenchMap) -> usize {
+        map.enhanced(50).lit_count()
+    }
+}
+
+/// How many pixels the enhancement algorithm has, one for each 3x3 neighbourhood.
+const ALGORITHM_SIZE: usize = 512;
+
+pub struct TrenchMap {
+    algorithm: Vec<bool>,
+    image: TrenchImage,
+}
+
+impl TrenchMap {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let blocks = parse::blocks(input);
+        let [algorithm, image] = blocks.as_slice() else {
+            return match blocks.get(2) {
+                Some(extra) => {
+                    let (lineno, text) = extra.lines()[0];
+                    Err(ParseError::new(
+                        lineno,
+                        text,
+                        text,
+                        "expected the end of the input",
+                    ))
+                }
+                None => Err(ParseError::unexpected_end(
+                    input,
+                    "expected the enhancement algorithm, a blank line and then the image",
+                )),
+            };
+        };
+
+        let algorithm = algorithm.parse_line(check(pixels, |algorithm: &Vec<bool>| {
+            if algorithm.len() != ALGORITHM_SIZE {
+                return Err(format!("expected {} pixels", ALGORITHM_SIZE));
+            }
+            if algorithm[0] && algorithm[ALGORITHM_SIZE - 1] {
+                // The infinite background would light up and stay lit.
+                return Err("expected the first or last pixel to be dark".to_string());
+            }
+            Ok(())
+        }))?;
+
+        let rows = image.parse_lines(pixels)?;
+        let width = rows[0].len();
+        for (&(lineno, text), row) in image.lines().iter().zip(&rows) {
+            if row.len() != width {
+                return Err(ParseError::new(
+                    lineno,
+                    text,
+                    text,
+                    format!("expected a row {} wide, like the first", width),
+                ));
+            }
+        }
+        let pixels = Grid::from_fn(width, rows.len(), |pos| rows[pos.y][pos.x]);
+
+        Ok(TrenchMap {
+            algorithm,
+            image: TrenchImage {
+                pixels,
+                background: false,
+            },
+        })
+    }
+
+    fn enhanced(&self, times: usize) -> TrenchImage {
+        let mut image = self.image.clone();
+        automaton::run(&mut image, times, |image| image.enhance(&self.algorithm));
+        image
+    }
+}
+
+/// Reads a whole line of `#` and `.` as lit and dark pixels.
+fn pixels(s: &str) -> Parsed<'_, Vec<bool>> {
+    let pixels = s
+        .char_indices()
+        .map(|(at, c)| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(Failure::new(
+                &s[at..at + c.len_utf8()],
+                "expected `#` or `.`",
+            )),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((pixels, &s[s.len()..]))
+}
+
+/// A finite patch of an infinite image, with every pixel outside it the same as
+/// `background`.
+#[derive(Clone)]
+pub struct TrenchImage {
+    pixels: Grid<bool>,
+    background: bool,
+}
+
+impl TrenchImage {
+    /// Applies the algorithm to every pixel, growing the patch by one on each
+    /// side since that's as far as the background's influence reaches. The
+    /// background all enhances the same way, which flips it if a dark
+    /// neighbourhood lights a pixel.
+    fn enhance(&mut self, algorithm: &[bool]) {
+        let (width, height) = (self.pixels.width() + 2, self.pixels.height() + 2);
+        let pixels = Grid::from_fn(width, height, |pos| {
+            // The new patch starts one pixel up and left of the old one.
+            let (x, y) = (pos.x as isize - 1, pos.y as isize - 1);
+            let index = (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+                .fold(0, |index, (x, y)| index << 1 | self.pixel(x, y) as usize);
+            algorithm[index]
+        });
+        let background = match self.background {
+            true => ALGORITHM_SIZE - 1,
+            false => 0,
+        };
+        self.background = algorithm[background];
+        self.pixels = pixels;
+    }
+
+    /// The pixel at `(x, y)` in the patch, or the background if that's outside it.
+    fn pixel(&self, x: isize, y: isize) -> bool {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) => self
+                .pixels
+                .get(Point { x, y })
+                .copied()
+                .unwrap_or(self.background),
+            _ => self.background,
+        }
+    }
+
+    fn lit_count(&self) -> usize {
+        assert!(!self.background, "infinitely many pixels are lit");
+        self.pixels.values().filter(|&&lit| lit).count()
+    }
+}
+
+/// A random algorithm that leaves the background dark after every other step,
+/// and a `scale` by `scale` image.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut algorithm = (0..ALGORITHM_SIZE)
+        .map(|_| rng.chance(0.5))
+        .collect::<Vec<_>>();
+    algorithm[ALGORITHM_SIZE - 1] &= !algorithm[0];
+    let pixel = |lit| if lit { '#' } else { '.' };
+    let mut input = algorithm.into_iter().map(pixel).collect::<String>();
+    input.push_str("\n\n");
+    for _ in 0..scale {
+        input.extend((0..scale).map(|_| pixel(rng.chance(0.5))));
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+#[test]
+fn example() {
+    assert_eq!((35, 3351), solve::<Day20>(EXAMPLE));
+}
+
+#[test]
+fn background_flips() {
+    // Only an all-dark neighbourhood lights up, so the background alternates,
+    // and after two steps only the pixel that started lit is lit.
+    let input = format!("#{}\n\n#\n", ".".repeat(ALGORITHM_SIZE - 1));
+    let map = Day20::parse(&input).unwrap();
+    let once = map.enhanced(1);
+    assert!(once.background);
+    assert_eq!((3, 3), (once.pixels.width(), once.pixels.height()));
+    assert_eq!(1, map.enhanced(2).lit_count());
+}
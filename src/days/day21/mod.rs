@@ -0,0 +1,158 @@
+use crate::automaton;
+use crate::error::ParseError;
+use crate::parse::{self, check, integer, pair, preceded};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use std::collections::HashMap;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Parsed = Game;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Game, ParseError> {
+        let position = check(integer("a position"), |&position: &usize| match position {
+            1..=TRACK_LENGTH => Ok(()),
+            _ => Err(format!("positions only go from 1 to {}", TRACK_LENGTH)),
+        });
+        let player = preceded(
+            "Player ",
+            pair(
+                integer::<usize>("a player number"),
+                " starting position: ",
+                position,
+            ),
+        );
+        let players = parse::lines(input, player)?;
+
+        for ((i, line), &(number, _)) in input.lines().enumerate().zip(&players) {
+            let message = match i {
+                0 | 1 if number == i + 1 => continue,
+                0 | 1 => format!("expected player {}", i + 1),
+                _ => "expected only two players".to_string(),
+            };
+            return Err(ParseError::new(i + 1, line, line, message));
+        }
+        if players.len() < 2 {
+            return Err(ParseError::unexpected_end(
+                input,
+                format!("expected player {}", players.len() + 1),
+            ));
+        }
+        Ok(Game::start([players[0].1, players[1].1]))
+    }
+
+    /// Plays with the deterministic die, which rolls 1 to 100 in order and then
+    /// starts again.
+    fn part1(start: &Game) -> usize {
+        let mut game = *start;
+        let mut die = (1..=100).cycle();
+        let mut rolls = 0;
+        while game.winner(1000).is_none() {
+            game = game.advance(die.by_ref().take(3).sum());
+            rolls += 3;
+        }
+        game.scores.iter().min().unwrap() * rolls
+    }
+
+    fn part2(start: &Game) -> usize {
+        let mut multiverse = Multiverse {
+            games: HashMap::from([(*start, 1)]),
+            wins: [0; 2],
+        };
+        automaton::run_until(&mut multiverse, Multiverse::step, |multiverse| {
+            multiverse.games.is_empty()
+        });
+        multiverse.wins.into_iter().max().unwrap()
+    }
+}
+
+const TRACK_LENGTH: usize = 10;
+
+/// The state of a game between turns.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Game {
+    positions: [usize; 2],
+    scores: [usize; 2],
+    /// Whose turn it is next.
+    turn: usize,
+}
+
+impl Game {
+    fn start(positions: [usize; 2]) -> Self {
+        Game {
+            positions,
+            scores: [0; 2],
+            turn: 0,
+        }
+    }
+
+    /// Moves the player whose turn it is `roll` spaces round the track.
+    fn advance(&self, roll: usize) -> Game {
+        let mut next = *self;
+        let player = self.turn;
+        next.positions[player] = (self.positions[player] + roll - 1) % TRACK_LENGTH + 1;
+        next.scores[player] += next.positions[player];
+        next.turn = 1 - player;
+        next
+    }
+
+    fn winner(&self, target: usize) -> Option<usize> {
+        self.scores.iter().position(|&score| score >= target)
+    }
+}
+
+/// Each total of three rolls of the Dirac die, with how many of the 27 ways of
+/// rolling it give that total.
+const DIRAC_ROLLS: [(usize, usize); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+/// How many universes are in each state of the game, rather than every
+/// universe on its own, since they keep ending up in the same states.
+#[derive(Clone)]
+struct Multiverse {
+    /// The games still being played.
+    games: HashMap<Game, usize>,
+    /// How many universes each player has won in so far.
+    wins: [usize; 2],
+}
+
+impl Multiverse {
+    /// Plays one turn in every unfinished game.
+    fn step(&mut self) {
+        let mut games = HashMap::new();
+        for (game, count) in self.games.drain() {
+            for (roll, ways) in DIRAC_ROLLS {
+                let next = game.advance(roll);
+                match next.winner(21) {
+                    Some(player) => self.wins[player] += count * ways,
+                    None => *games.entry(next).or_default() += count * ways,
+                }
+            }
+        }
+        self.games = games;
+    }
+}
+
+/// Two players starting anywhere on the track. There's nothing to scale.
+pub fn generate(rng: &mut Rng, _scale: usize) -> String {
+    (1..=2)
+        .map(|player| {
+            let position = rng.range(1..TRACK_LENGTH as i64 + 1);
+            format!("Player {} starting position: {}\n", player, position)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+Player 1 starting position: 4
+Player 2 starting position: 8";
+
+#[test]
+fn example() {
+    assert_eq!((739785, 444356092776315), solve::<Day21>(EXAMPLE));
+}
@@ -0,0 +1,354 @@
+use crate::error::ParseError;
+use crate::parse::{self, check, choice, integer, map, pair, preceded, Parser};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use std::collections::HashMap;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed = Vec<Step>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Step>, ParseError> {
+        let step = pair(
+            choice(&[("on", true), ("off", false)]),
+            " ",
+            Cuboid::parser(),
+        );
+        let steps = parse::lines(input, map(step, |(on, cuboid)| Step { on, cuboid }))?;
+        if steps.is_empty() {
+            return Err(ParseError::unexpected_end(input, "expected a reboot step"));
+        }
+        Ok(steps)
+    }
+
+    fn part1(steps: &Vec<Step>) -> i64 {
+        let clipped = steps.iter().filter_map(|step| {
+            let cuboid = step.cuboid.intersection(&INITIALISATION_REGION)?;
+            Some(Step { cuboid, ..*step })
+        });
+        lit_cubes(clipped)
+    }
+
+    fn part2(steps: &Vec<Step>) -> i64 {
+        lit_cubes(steps.iter().copied())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Step {
+    on: bool,
+    cuboid: Cuboid,
+}
+
+/// A box of cubes, inclusive of `min` and `max` along each axis.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Cuboid {
+    min: [i64; 3],
+    max: [i64; 3],
+}
+
+impl Cuboid {
+    /// Reads `x=min..max,y=min..max,z=min..max`.
+    fn parser<'a>() -> impl Parser<'a, Cuboid> {
+        let range = |prefix| {
+            let coordinate = || {
+                check(integer("a coordinate"), |&coordinate: &i64| {
+                    if coordinate.unsigned_abs() > MAX_COORDINATE as u64 {
+                        return Err(format!(
+                            "coordinates only go from -{0} to {0}",
+                            MAX_COORDINATE
+                        ));
+                    }
+                    Ok(())
+                })
+            };
+            let bounds = pair(coordinate(), "..", coordinate());
+            check(preceded(prefix, bounds), |&(min, max)| {
+                if min > max {
+                    return Err("expected the smaller bound first".to_string());
+                }
+                Ok(())
+            })
+        };
+        let ranges = pair(range("x="), ",", pair(range("y="), ",", range("z=")));
+        map(ranges, |(x, (y, z))| Cuboid {
+            min: [x.0, y.0, z.0],
+            max: [x.1, y.1, z.1],
+        })
+    }
+
+    fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let mut overlap = *self;
+        for axis in 0..3 {
+            overlap.min[axis] = self.min[axis].max(other.min[axis]);
+            overlap.max[axis] = self.max[axis].min(other.max[axis]);
+            if overlap.min[axis] > overlap.max[axis] {
+                return None;
+            }
+        }
+        Some(overlap)
+    }
+
+    fn volume(&self) -> i64 {
+        (0..3)
+            .map(|axis| self.max[axis] - self.min[axis] + 1)
+            .product()
+    }
+}
+
+/// Keeps any one cuboid's volume, and so the number of cubes left on, inside an
+/// `i64`. The signed counts on the way there can add up to far more.
+const MAX_COORDINATE: i64 = 999_999;
+
+const INITIALISATION_REGION: Cuboid = Cuboid {
+    min: [-50; 3],
+    max: [50; 3],
+};
+
+/// Counts the cubes left on after every step, without visiting them one by one.
+/// Each cuboid is counted with a sign: turning a cuboid on or off first takes
+/// away its overlap with everything counted so far, so that overlap counts
+/// once less, and then a cuboid turned on is counted itself. The counts for
+/// each distinct cuboid are kept together, since the same overlaps come up
+/// again and again.
+fn lit_cubes(steps: impl Iterator<Item = Step>) -> i64 {
+    let mut counts: HashMap<Cuboid, i64> = HashMap::new();
+    for step in steps {
+        let mut changes: HashMap<Cuboid, i64> = HashMap::new();
+        for (cuboid, &count) in &counts {
+            if let Some(overlap) = cuboid.intersection(&step.cuboid) {
+                *changes.entry(overlap).or_default() -= count;
+            }
+        }
+        if step.on {
+            *changes.entry(step.cuboid).or_default() += 1;
+        }
+        for (cuboid, change) in changes {
+            *counts.entry(cuboid).or_default() += change;
+        }
+        counts.retain(|_, count| *count != 0);
+    }
+    let lit = counts
+        .iter()
+        .map(|(cuboid, &count)| cuboid.volume() as i128 * count as i128)
+        .sum::<i128>();
+    // No more cubes can be on than fit in the space the coordinates allow.
+    i64::try_from(lit).expect("more cubes lit than there are")
+}
+
+/// `scale` steps, starting with a few around the initialisation region and
+/// then ranging out to the size of the real reactor.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|i| {
+            let (reach, size) = match i < scale / 4 + 1 {
+                true => (50, 30),
+                false => (100_000, 30_000),
+            };
+            let ranges = ["x", "y", "z"].map(|axis| {
+                let min = rng.range(-reach..reach);
+                let max = min + rng.range(0..size);
+                format!("{}={}..{}", axis, min, max)
+            });
+            let on = i == 0 || rng.chance(0.6);
+            format!("{} {}\n", if on { "on" } else { "off" }, ranges.join(","))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10";
+
+#[cfg(test)]
+const LARGER_EXAMPLE: &str = "\
+on x=-20..26,y=-36..17,z=-47..7
+on x=-20..33,y=-21..23,z=-26..28
+on x=-22..28,y=-29..23,z=-38..16
+on x=-46..7,y=-6..46,z=-50..-1
+on x=-49..1,y=-3..46,z=-24..28
+on x=2..47,y=-22..22,z=-23..27
+on x=-27..23,y=-28..26,z=-21..29
+on x=-39..5,y=-6..47,z=-3..44
+on x=-30..21,y=-8..43,z=-13..34
+on x=-22..26,y=-27..20,z=-29..19
+off x=-48..-32,y=26..41,z=-47..-37
+on x=-12..35,y=6..50,z=-50..-2
+off x=-48..-32,y=-32..-16,z=-15..-5
+on x=-18..26,y=-33..15,z=-7..46
+off x=-40..-22,y=-38..-28,z=23..41
+on x=-16..35,y=-41..10,z=-47..6
+off x=-32..-23,y=11..30,z=-14..3
+on x=-49..-5,y=-3..45,z=-29..18
+off x=18..30,y=-20..-8,z=-3..13
+on x=-41..9,y=-7..43,z=-33..15
+on x=-54112..-39298,y=-85059..-49293,z=-27449..7877
+on x=967..23432,y=45373..81175,z=27513..53682";
+
+#[cfg(test)]
+const REBOOT_EXAMPLE: &str = "\
+on x=-5..47,y=-31..22,z=-19..33
+on x=-44..5,y=-27..21,z=-14..35
+on x=-49..-1,y=-11..42,z=-10..38
+on x=-20..34,y=-40..6,z=-44..1
+off x=26..39,y=40..50,z=-2..11
+on x=-41..5,y=-41..6,z=-36..8
+off x=-43..-33,y=-45..-28,z=7..25
+on x=-33..15,y=-32..19,z=-34..11
+off x=35..47,y=-46..-34,z=-11..5
+on x=-14..36,y=-6..44,z=-16..29
+on x=-57795..-6158,y=29564..72030,z=20435..90618
+on x=36731..105352,y=-21140..28532,z=16094..90401
+on x=30999..107136,y=-53464..15513,z=8553..71215
+on x=13528..83982,y=-99403..-27377,z=-24141..23996
+on x=-72682..-12347,y=18159..111354,z=7391..80950
+on x=-1060..80757,y=-65301..-20884,z=-103788..-16709
+on x=-83015..-9461,y=-72160..-8347,z=-81239..-26856
+on x=-52752..22273,y=-49450..9096,z=54442..119054
+on x=-29982..40483,y=-108474..-28371,z=-24328..38471
+on x=-4958..62750,y=40422..118853,z=-7672..65583
+on x=55694..108686,y=-43367..46958,z=-26781..48729
+on x=-98497..-18186,y=-63569..3412,z=1232..88485
+on x=-726..56291,y=-62629..13224,z=18033..85226
+on x=-110886..-34664,y=-81338..-8658,z=8914..63723
+on x=-55829..24974,y=-16897..54165,z=-121762..-28058
+on x=-65152..-11147,y=22489..91432,z=-58782..1780
+on x=-120100..-32970,y=-46592..27473,z=-11695..61039
+on x=-18631..37533,y=-124565..-50804,z=-35667..28308
+on x=-57817..18248,y=49321..117703,z=5745..55881
+on x=14781..98692,y=-1341..70827,z=15753..70151
+on x=-34419..55919,y=-19626..40991,z=39015..114138
+on x=-60785..11593,y=-56135..2999,z=-95368..-26915
+on x=-32178..58085,y=17647..101866,z=-91405..-8878
+on x=-53655..12091,y=50097..105568,z=-75335..-4862
+on x=-111166..-40997,y=-71714..2688,z=5609..50954
+on x=-16602..70118,y=-98693..-44401,z=5197..76897
+on x=16383..101554,y=4615..83635,z=-44907..18747
+off x=-95822..-15171,y=-19987..48940,z=10804..104439
+on x=-89813..-14614,y=16069..88491,z=-3297..45228
+on x=41075..99376,y=-20427..49978,z=-52012..13762
+on x=-21330..50085,y=-17944..62733,z=-112280..-30197
+on x=-16478..35915,y=36008..118594,z=-7885..47086
+off x=-98156..-27851,y=-49952..43171,z=-99005..-8456
+off x=2032..69770,y=-71013..4824,z=7471..94418
+on x=43670..120875,y=-42068..12382,z=-24787..38892
+off x=37514..111226,y=-45862..25743,z=-16714..54663
+off x=25699..97951,y=-30668..59918,z=-15349..69697
+off x=-44271..17935,y=-9516..60759,z=49131..112598
+on x=-61695..-5813,y=40978..94975,z=8655..80240
+off x=-101086..-9439,y=-7088..67543,z=33935..83858
+off x=18020..114017,y=-48931..32606,z=21474..89843
+off x=-77139..10506,y=-89994..-18797,z=-80..59318
+off x=8476..79288,y=-75520..11602,z=-96624..-24783
+on x=-47488..-1262,y=24338..100707,z=16292..72967
+off x=-84341..13987,y=2429..92914,z=-90671..-1318
+off x=-37810..49457,y=-71013..-7894,z=-105357..-13188
+off x=-27365..46395,y=31009..98017,z=15428..76570
+off x=-70369..-16548,y=22648..78696,z=-1892..86821
+on x=-53470..21291,y=-120233..-33476,z=-44150..38147
+off x=-93533..-4276,y=-16170..68771,z=-104985..-24507";
+
+#[test]
+fn example() {
+    assert_eq!((39, 39), solve::<Day22>(EXAMPLE));
+    assert_eq!(590784, Day22::part1(&Day22::parse(LARGER_EXAMPLE).unwrap()));
+    assert_eq!((474140, 2758514936282235), solve::<Day22>(REBOOT_EXAMPLE));
+    assert!(Day22::parse("").is_err());
+}
+
+#[test]
+fn matches_switching_every_cube() {
+    use std::collections::HashSet;
+
+    let mut rng = Rng::new(22);
+    for _ in 0..20 {
+        let steps = (0..8)
+            .map(|_| {
+                let min = [(); 3].map(|_| rng.range(-5..5));
+                Step {
+                    on: rng.chance(0.6),
+                    cuboid: Cuboid {
+                        min,
+                        max: min.map(|min| min + rng.range(0..5)),
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut lit = HashSet::new();
+        for step in &steps {
+            let Cuboid { min, max } = step.cuboid;
+            for x in min[0]..=max[0] {
+                for y in min[1]..=max[1] {
+                    for z in min[2]..=max[2] {
+                        match step.on {
+                            true => lit.insert([x, y, z]),
+                            false => lit.remove(&[x, y, z]),
+                        };
+                    }
+                }
+            }
+        }
+        assert_eq!(lit.len() as i64, lit_cubes(steps.into_iter()));
+    }
+}
+
+#[test]
+fn generated_steps_match_switching_every_cube() {
+    use std::collections::HashSet;
+
+    for seed in 0..5 {
+        let input = generate(&mut Rng::new(seed), 8);
+        let steps = Day22::parse(&input).unwrap();
+        let mut lit = HashSet::new();
+        for step in &steps {
+            let Some(Cuboid { min, max }) = step.cuboid.intersection(&INITIALISATION_REGION) else {
+                continue;
+            };
+            for x in min[0]..=max[0] {
+                for y in min[1]..=max[1] {
+                    for z in min[2]..=max[2] {
+                        match step.on {
+                            true => lit.insert([x, y, z]),
+                            false => lit.remove(&[x, y, z]),
+                        };
+                    }
+                }
+            }
+        }
+        assert_ne!(0, lit.len());
+        assert_eq!(lit.len() as i64, Day22::part1(&steps));
+    }
+}
+
+#[test]
+fn rejects_coordinates_too_far_out() {
+    for step in [
+        "on x=-9223372036854775808..0,y=0..0,z=0..0",
+        "off x=0..0,y=0..1000000,z=0..0",
+    ] {
+        let error = Day22::parse(step).unwrap_err();
+        assert_eq!("coordinates only go from -999999 to 999999", error.message);
+    }
+}
+
+#[test]
+fn counts_overlapping_cuboids_as_big_as_allowed() {
+    // Together they fill the whole space allowed, which only just fits in an
+    // i64, but the signed counts on the way there add up to more.
+    let steps = "\
+on x=-999999..999998,y=-999999..999999,z=-999999..999999
+on x=-999998..999999,y=-999999..999999,z=-999999..999999";
+    let side = 1_999_999i64;
+    assert_eq!(
+        side * side * side,
+        Day22::part2(&Day22::parse(steps).unwrap())
+    );
+}
@@ -0,0 +1,444 @@
+use crate::error::ParseError;
+use crate::random::Rng;
+use crate::search;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::{Answer, Solution};
+use array_init::array_init;
+use std::fmt::{self, Display, Formatter};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Parsed = Burrow<2>;
+    type Answer1 = Answer<usize>;
+    type Answer2 = Answer<usize>;
+
+    fn parse(input: &str) -> Result<Burrow<2>, ParseError> {
+        Burrow::from_diagram(input)
+    }
+
+    fn part1(burrow: &Burrow<2>) -> Answer<usize> {
+        Answer::found_or(burrow.least_energy_to_organise(), DEADLOCKED)
+    }
+
+    fn part2(burrow: &Burrow<2>) -> Answer<usize> {
+        Answer::found_or(burrow.unfold().least_energy_to_organise(), DEADLOCKED)
+    }
+}
+
+const DEADLOCKED: &str = "the amphipods are deadlocked";
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+use Amphipod::*;
+
+impl Amphipod {
+    const ALL: [Amphipod; ROOMS] = [Amber, Bronze, Copper, Desert];
+
+    fn from_letter(letter: char) -> Option<Amphipod> {
+        Amphipod::ALL
+            .into_iter()
+            .find(|amphipod| amphipod.letter() == letter)
+    }
+
+    fn letter(self) -> char {
+        (b'A' + self as u8) as char
+    }
+
+    /// Which room it belongs in, counting from the left.
+    fn room(self) -> usize {
+        self as usize
+    }
+
+    /// The energy it takes to move one space.
+    fn energy(self) -> usize {
+        10usize.pow(self as u32)
+    }
+}
+
+const ROOMS: usize = 4;
+const HALLWAY_LENGTH: usize = 11;
+
+/// Where in the hallway the door to `room` is.
+fn door(room: usize) -> usize {
+    2 + 2 * room
+}
+
+/// The burrow as drawn in the input, with `?` wherever an amphipod goes.
+const DIAGRAM: [&str; 5] = [
+    "#############",
+    "#...........#",
+    "###?#?#?#?###",
+    "  #?#?#?#?#",
+    "  #########",
+];
+
+/// Where every amphipod is, with rooms `DEPTH` spaces deep. Each space takes
+/// three bits, empty or which amphipod is in it, so a whole burrow packs into
+/// one integer: the hallway first, then each room from the left, from the door
+/// to the back.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Burrow<const DEPTH: usize> {
+    spaces: u128,
+}
+
+impl Burrow<2> {
+    fn from_diagram(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines().enumerate();
+        let mut found = vec![];
+        for expected in DIAGRAM {
+            let Some((i, text)) = lines.next() else {
+                return Err(ParseError::unexpected_end(
+                    input,
+                    format!("expected `{}`", expected),
+                ));
+            };
+            let text = text.trim_end();
+            for (column, wanted) in expected.char_indices() {
+                // Everything before here matched, so this is a char boundary.
+                let rest = &text[column..];
+                let next = rest.chars().next();
+                let at = &rest[..next.map_or(0, char::len_utf8)];
+                match (wanted, next.and_then(Amphipod::from_letter)) {
+                    ('?', Some(amphipod)) => found.push((amphipod, i + 1, text, at)),
+                    ('?', None) => {
+                        let message = "expected an amphipod, `A` to `D`";
+                        return Err(ParseError::new(i + 1, text, at, message));
+                    }
+                    _ if next == Some(wanted) => {}
+                    _ => {
+                        let message = format!("expected `{}`", wanted);
+                        return Err(ParseError::new(i + 1, text, at, message));
+                    }
+                }
+            }
+            if text.len() > expected.len() {
+                let at = &text[expected.len()..];
+                return Err(ParseError::new(
+                    i + 1,
+                    text,
+                    at,
+                    "expected the end of the line",
+                ));
+            }
+        }
+        if let Some((i, text)) = lines.find(|(_, text)| !text.trim().is_empty()) {
+            return Err(ParseError::new(
+                i + 1,
+                text,
+                text,
+                "expected the end of the input",
+            ));
+        }
+
+        let mut burrow = Burrow::EMPTY;
+        for (n, &(amphipod, lineno, text, at)) in found.iter().enumerate() {
+            let earlier = found[..n].iter().filter(|found| found.0 == amphipod);
+            if earlier.count() == 2 {
+                let message = format!("expected only two of `{}`", amphipod.letter());
+                return Err(ParseError::new(lineno, text, at, message));
+            }
+            burrow.set_room(n % ROOMS, n / ROOMS, Some(amphipod));
+        }
+        Ok(burrow)
+    }
+
+    /// Unfolds the diagram, revealing two more rows of amphipods in the middle
+    /// of each room.
+    fn unfold(&self) -> Burrow<4> {
+        let hidden = [
+            [Desert, Desert],
+            [Copper, Bronze],
+            [Bronze, Amber],
+            [Amber, Copper],
+        ];
+        let mut unfolded = Burrow::EMPTY;
+        for x in 0..HALLWAY_LENGTH {
+            unfolded.set_hallway(x, self.hallway(x));
+        }
+        for (room, [second, third]) in hidden.into_iter().enumerate() {
+            unfolded.set_room(room, 0, self.room(room, 0));
+            unfolded.set_room(room, 1, Some(second));
+            unfolded.set_room(room, 2, Some(third));
+            unfolded.set_room(room, 3, self.room(room, 1));
+        }
+        unfolded
+    }
+}
+
+impl<const DEPTH: usize> Burrow<DEPTH> {
+    const EMPTY: Self = Burrow { spaces: 0 };
+
+    fn get(&self, space: usize) -> Option<Amphipod> {
+        match (self.spaces >> (3 * space)) & 0b111 {
+            0 => None,
+            n => Some(Amphipod::ALL[n as usize - 1]),
+        }
+    }
+
+    fn set(&mut self, space: usize, amphipod: Option<Amphipod>) {
+        let bits = amphipod.map_or(0, |amphipod| amphipod as u128 + 1);
+        self.spaces = self.spaces & !(0b111 << (3 * space)) | bits << (3 * space);
+    }
+
+    fn hallway(&self, x: usize) -> Option<Amphipod> {
+        self.get(x)
+    }
+
+    fn set_hallway(&mut self, x: usize, amphipod: Option<Amphipod>) {
+        self.set(x, amphipod)
+    }
+
+    /// The space `depth` into `room`, counting from the door.
+    fn room(&self, room: usize, depth: usize) -> Option<Amphipod> {
+        self.get(HALLWAY_LENGTH + room * DEPTH + depth)
+    }
+
+    fn set_room(&mut self, room: usize, depth: usize, amphipod: Option<Amphipod>) {
+        self.set(HALLWAY_LENGTH + room * DEPTH + depth, amphipod)
+    }
+
+    /// The least energy it takes, or `None` if the amphipods are deadlocked.
+    fn least_energy_to_organise(&self) -> Option<usize> {
+        search::astar(
+            *self,
+            Burrow::moves,
+            Burrow::energy_still_needed,
+            Burrow::is_organised,
+        )
+        .map(|path| path.cost)
+    }
+
+    /// A lower bound on the energy still needed: every amphipod not yet settled
+    /// at home walking to its own door as if the hallway were empty, and then
+    /// filling its room from the back.
+    fn energy_still_needed(&self) -> usize {
+        // Everyone above a stranger has to leave too, to let it out.
+        let settled: [usize; ROOMS] = array_init(|room| {
+            (0..DEPTH)
+                .rev()
+                .take_while(|&depth| {
+                    self.room(room, depth)
+                        .is_some_and(|amphipod| amphipod.room() == room)
+                })
+                .count()
+        });
+        let mut energy = 0;
+        let mut arriving = [0; ROOMS];
+        for x in 0..HALLWAY_LENGTH {
+            if let Some(amphipod) = self.hallway(x) {
+                energy += x.abs_diff(door(amphipod.room())) * amphipod.energy();
+                arriving[amphipod.room()] += 1;
+            }
+        }
+        for (room, settled) in settled.iter().enumerate() {
+            for depth in 0..DEPTH - settled {
+                let Some(amphipod) = self.room(room, depth) else {
+                    continue;
+                };
+                // Even an amphipod leaving its own room has to step aside.
+                let along = door(room).abs_diff(door(amphipod.room())).max(2);
+                energy += (depth + 1 + along) * amphipod.energy();
+                arriving[amphipod.room()] += 1;
+            }
+        }
+        for (room, arriving) in arriving.into_iter().enumerate() {
+            let space = DEPTH - settled[room];
+            let steps: usize = (space - arriving + 1..=space).sum();
+            energy += steps * Amphipod::ALL[room].energy();
+        }
+        energy
+    }
+
+    fn is_organised(&self) -> bool {
+        Amphipod::ALL.iter().all(|&amphipod| {
+            (0..DEPTH).all(|depth| self.room(amphipod.room(), depth) == Some(amphipod))
+        })
+    }
+
+    /// Every burrow one move away, with the energy the move takes. Amphipods
+    /// only leave a room that holds any of another type, stop in the hallway
+    /// anywhere but outside a door, and from there only go straight into their
+    /// own room once no strangers are left in it.
+    ///
+    /// An amphipod that can go home never has to move again and can only free
+    /// up the hallway by going, so when one can, that's the only move offered.
+    /// Nothing is offered once two amphipods in the hallway each need to get
+    /// past the other.
+    fn moves(&self) -> Vec<(Burrow<DEPTH>, usize)> {
+        if self.hallway_blocked() {
+            return vec![];
+        }
+        for x in 0..HALLWAY_LENGTH {
+            let Some(amphipod) = self.hallway(x) else {
+                continue;
+            };
+            let room = amphipod.room();
+            let Some(depth) = self.space_to_enter(room) else {
+                continue;
+            };
+            if !self.hallway_clear(x, door(room)) {
+                continue;
+            }
+            let mut next = *self;
+            next.set_hallway(x, None);
+            next.set_room(room, depth, Some(amphipod));
+            let steps = x.abs_diff(door(room)) + depth + 1;
+            return vec![(next, steps * amphipod.energy())];
+        }
+
+        let mut moves = vec![];
+        for room in 0..ROOMS {
+            let Some(depth) = (0..DEPTH).find(|&depth| self.room(room, depth).is_some()) else {
+                continue;
+            };
+            if self.only_own(room) {
+                // Everyone here is already home.
+                continue;
+            }
+            let amphipod = self.room(room, depth).unwrap();
+            for x in (0..HALLWAY_LENGTH).filter(|&x| (0..ROOMS).all(|room| door(room) != x)) {
+                if !self.hallway_clear(door(room), x) {
+                    continue;
+                }
+                let mut next = *self;
+                next.set_room(room, depth, None);
+                next.set_hallway(x, Some(amphipod));
+                let steps = depth + 1 + x.abs_diff(door(room));
+                moves.push((next, steps * amphipod.energy()));
+            }
+        }
+        moves
+    }
+
+    /// Whether two amphipods in the hallway are each in the other's way home.
+    fn hallway_blocked(&self) -> bool {
+        let heading = |x| self.hallway(x).map(|amphipod| door(amphipod.room()));
+        (0..HALLWAY_LENGTH).any(|left| {
+            heading(left).is_some_and(|left_door| {
+                (left + 1..left_door).any(|right| heading(right).is_some_and(|door| door < left))
+            })
+        })
+    }
+
+    /// Whether every amphipod in `room` belongs there.
+    fn only_own(&self, room: usize) -> bool {
+        (0..DEPTH).all(|depth| {
+            self.room(room, depth)
+                .is_none_or(|amphipod| amphipod.room() == room)
+        })
+    }
+
+    /// The space at the back of `room` its own amphipods would move into next,
+    /// if there are no others in it.
+    fn space_to_enter(&self, room: usize) -> Option<usize> {
+        match self.only_own(room) {
+            true => (0..DEPTH)
+                .rev()
+                .find(|&depth| self.room(room, depth).is_none()),
+            false => None,
+        }
+    }
+
+    /// Whether the hallway is empty between `from` and `to`, not counting `from`.
+    fn hallway_clear(&self, from: usize, to: usize) -> bool {
+        let mut between = match from < to {
+            true => from + 1..to + 1,
+            false => to..from,
+        };
+        between.all(|x| self.hallway(x).is_none())
+    }
+}
+
+impl<const DEPTH: usize> Display for Burrow<DEPTH> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let letter = |space: Option<Amphipod>| space.map_or('.', Amphipod::letter);
+        writeln!(f, "{}", DIAGRAM[0])?;
+        let hallway = (0..HALLWAY_LENGTH)
+            .map(|x| letter(self.hallway(x)))
+            .collect::<String>();
+        writeln!(f, "#{}#", hallway)?;
+        for depth in 0..DEPTH {
+            let row = (0..ROOMS)
+                .map(|room| letter(self.room(room, depth)).to_string())
+                .collect::<Vec<_>>();
+            match depth {
+                0 => writeln!(f, "###{}###", row.join("#"))?,
+                _ => writeln!(f, "  #{}#", row.join("#"))?,
+            }
+        }
+        writeln!(f, "{}", DIAGRAM[4])
+    }
+}
+
+/// Two of each amphipod shuffled between the rooms, reshuffled until they can
+/// all get home, both as drawn and unfolded. The burrow is always the same size,
+/// so there's nothing to scale.
+pub fn generate(rng: &mut Rng, _scale: usize) -> String {
+    loop {
+        let mut amphipods = Amphipod::ALL.repeat(2);
+        rng.shuffle(&mut amphipods);
+        let mut burrow = Burrow::<2>::EMPTY;
+        for (n, amphipod) in amphipods.into_iter().enumerate() {
+            burrow.set_room(n % ROOMS, n / ROOMS, Some(amphipod));
+        }
+        if burrow.least_energy_to_organise().is_some()
+            && burrow.unfold().least_energy_to_organise().is_some()
+        {
+            return burrow.to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+#[test]
+fn example() {
+    assert_eq!(
+        (Answer::Found(12521), Answer::Found(44169)),
+        solve::<Day23>(EXAMPLE)
+    );
+}
+
+#[test]
+fn diagram_round_trip() {
+    let burrow = Burrow::from_diagram(EXAMPLE).unwrap();
+    assert_eq!(format!("{}\n", EXAMPLE), burrow.to_string());
+    let error = Day23::parse(&EXAMPLE.replace("#C#A#", "#B#A#")).unwrap_err();
+    assert_eq!(
+        (4, 8, "expected only two of `B`"),
+        (error.line, error.column, error.message.as_str())
+    );
+}
+
+#[test]
+fn no_answer_once_deadlocked() {
+    let deadlocked = "\
+#############
+#...........#
+###C#D#B#C###
+  #B#A#D#A#
+  #########
+";
+    // Once unfolded, the amphipods in the hallway end up in each other's way.
+    assert_eq!(
+        (
+            Answer::Found(13421),
+            Answer::Impossible(DEADLOCKED.to_string())
+        ),
+        solve::<Day23>(deadlocked)
+    );
+}
@@ -0,0 +1,441 @@
+use crate::answers::Answers;
+use crate::error::ParseError;
+use crate::parse::{self, choice, integer, map, pair, preceded, Parsed};
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
+use crate::work;
+use itertools::Itertools;
+use std::collections::HashSet;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Parsed = Monad;
+    type Answer1 = Answer<u64>;
+    type Answer2 = Answer<u64>;
+
+    fn parse(input: &str) -> Result<Monad, ParseError> {
+        Monad::from_program(input, MODEL_NUMBER_LENGTH)
+    }
+
+    fn part1(monad: &Monad) -> Answer<u64> {
+        let largest = monad.find_model_number([9, 8, 7, 6, 5, 4, 3, 2, 1]);
+        Answer::found_or(largest, NONE_ACCEPTED)
+    }
+
+    fn part2(monad: &Monad) -> Answer<u64> {
+        let smallest = monad.find_model_number([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        Answer::found_or(smallest, NONE_ACCEPTED)
+    }
+}
+
+const NONE_ACCEPTED: &str = "MONAD accepts no model number";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+const REGISTERS: &[(&str, Register)] = &[
+    ("w", Register::W),
+    ("x", Register::X),
+    ("y", Register::Y),
+    ("z", Register::Z),
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operand {
+    Register(Register),
+    Number(i64),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operation {
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+/// Each instruction's name, with `None` for `inp`.
+const OPERATIONS: &[(&str, Option<Operation>)] = &[
+    ("inp", None),
+    ("add", Some(Operation::Add)),
+    ("mul", Some(Operation::Mul)),
+    ("div", Some(Operation::Div)),
+    ("mod", Some(Operation::Mod)),
+    ("eql", Some(Operation::Eql)),
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Instruction {
+    /// Reads the next input into the register.
+    Input(Register),
+    /// Applies the operation to the register and the operand, storing the
+    /// result in the register.
+    Binary(Operation, Register, Operand),
+}
+
+impl Instruction {
+    fn parse(s: &str) -> Parsed<'_, Instruction> {
+        let (operation, rest) = choice(OPERATIONS)(s)?;
+        let register = || choice(REGISTERS);
+        match operation {
+            None => map(preceded(" ", register()), Instruction::Input)(rest),
+            Some(operation) => {
+                let operand = |s| match register()(s) {
+                    Ok((register, rest)) => Ok((Operand::Register(register), rest)),
+                    Err(_) => map(integer("a register or a number"), Operand::Number)(s),
+                };
+                let operands = preceded(" ", pair(register(), " ", operand));
+                map(operands, |(a, b)| Instruction::Binary(operation, a, b))(rest)
+            }
+        }
+    }
+}
+
+/// The arithmetic logic unit's four registers.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Alu {
+    registers: [i64; 4],
+}
+
+impl Alu {
+    /// Runs `instructions` in turn, taking each input from `input`. Gives `None`
+    /// if the program crashes, by dividing by zero, taking a modulus the puzzle
+    /// doesn't allow, overflowing, or running out of input.
+    fn run<'i>(
+        &mut self,
+        instructions: impl IntoIterator<Item = &'i Instruction>,
+        mut input: impl Iterator<Item = i64>,
+    ) -> Option<()> {
+        for instruction in instructions {
+            match *instruction {
+                Instruction::Input(a) => self.registers[a as usize] = input.next()?,
+                Instruction::Binary(operation, a, b) => {
+                    let b = self.value(b);
+                    let a = &mut self.registers[a as usize];
+                    *a = match operation {
+                        Operation::Add => a.checked_add(b)?,
+                        Operation::Mul => a.checked_mul(b)?,
+                        Operation::Div => a.checked_div(b)?,
+                        Operation::Mod if *a < 0 || b <= 0 => return None,
+                        Operation::Mod => *a % b,
+                        Operation::Eql => (*a == b) as i64,
+                    };
+                }
+            }
+        }
+        Some(())
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.registers[register as usize],
+            Operand::Number(n) => n,
+        }
+    }
+}
+
+const MODEL_NUMBER_LENGTH: usize = 14;
+
+/// One block of MONAD, checking one digit. Only three numbers differ between
+/// blocks: what `z` is divided by, what's added to `x` before it's compared
+/// with the digit, and what's added to the digit before it goes onto `z`.
+fn block(divisor: i64, check: i64, offset: i64) -> String {
+    format!(
+        "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\n\
+         mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\n\
+         mul y 0\nadd y w\nadd y {}\nmul y x\nadd z y\n",
+        divisor, check, offset
+    )
+}
+
+const BLOCK_LENGTH: usize = 18;
+
+/// Where in a block the divisor, check and offset are.
+const DIVISOR_LINE: usize = 4;
+const CHECK_LINE: usize = 5;
+const OFFSET_LINE: usize = 15;
+
+/// The MONAD program, split into the blocks that each check one digit of the
+/// model number, starting at their `inp`.
+#[derive(Debug)]
+pub struct Monad {
+    blocks: Vec<Vec<Instruction>>,
+    /// For each block, how many times over `z` can be divided from there to
+    /// the end: the product of every divisor left.
+    shrinkage: Vec<i64>,
+}
+
+impl Monad {
+    /// Parses a program reading `digits` digits, each in a block shaped like
+    /// [`block`]'s, dividing `z` by a positive number and never subtracting
+    /// from it.
+    fn from_program(input: &str, digits: usize) -> Result<Self, ParseError> {
+        let instructions = parse::lines(input, Instruction::parse)?;
+        let lines = input.lines().collect::<Vec<_>>();
+        let mut blocks = vec![];
+        let mut divisors = vec![];
+        for (n, instructions) in instructions.chunks(BLOCK_LENGTH).enumerate() {
+            let start = n * BLOCK_LENGTH;
+            let error = |i: usize, message: String| {
+                let text = lines[start + i];
+                Err(ParseError::new(start + i + 1, text, text, message))
+            };
+            if n == digits {
+                return error(0, format!("expected only {} digits to be read", digits));
+            }
+            let number = |i| match instructions.get(i) {
+                Some(&Instruction::Binary(_, _, Operand::Number(n))) => n,
+                _ => 0,
+            };
+            let (divisor, offset) = (number(DIVISOR_LINE), number(OFFSET_LINE));
+            let expected = block(divisor, number(CHECK_LINE), offset);
+            for (i, expected) in expected.lines().enumerate() {
+                let message = match [DIVISOR_LINE, CHECK_LINE, OFFSET_LINE].contains(&i) {
+                    true => format!(
+                        "expected `{}` and a number",
+                        expected.rsplit_once(' ').unwrap().0
+                    ),
+                    false => format!("expected `{}`", expected),
+                };
+                let Some(&instruction) = instructions.get(i) else {
+                    return Err(ParseError::unexpected_end(input, message));
+                };
+                if Instruction::parse(expected).ok() != Some((instruction, "")) {
+                    return error(i, message);
+                }
+            }
+            if divisor < 1 {
+                return error(DIVISOR_LINE, "expected a positive divisor".to_string());
+            }
+            if offset < 0 {
+                return error(OFFSET_LINE, "expected an offset of at least 0".to_string());
+            }
+            blocks.push(instructions.to_vec());
+            divisors.push(divisor);
+        }
+        if blocks.len() < digits {
+            return Err(ParseError::unexpected_end(
+                input,
+                format!(
+                    "expected {} digits to be read, found {}",
+                    digits,
+                    blocks.len()
+                ),
+            ));
+        }
+        let mut shrinkage: Vec<i64> = vec![1; blocks.len() + 1];
+        for (i, &divisor) in divisors.iter().enumerate().rev() {
+            shrinkage[i] = shrinkage[i + 1].saturating_mul(divisor);
+        }
+        Ok(Monad { blocks, shrinkage })
+    }
+
+    /// The first model number MONAD accepts, trying each digit in the order
+    /// given.
+    fn find_model_number(&self, digits: [i64; 9]) -> Option<u64> {
+        let mut dead_ends = HashSet::new();
+        let number = self.search(0, 0, &digits, &mut dead_ends)?;
+        Some(
+            number
+                .into_iter()
+                .rev()
+                .fold(0, |n, digit| n * 10 + digit as u64),
+        )
+    }
+
+    /// The digits from `block` on that MONAD accepts, given the `z` the blocks
+    /// before left, in reverse. Only `z` carries over between blocks, so once
+    /// a block has failed with some `z`, it'll fail with it again, and is noted
+    /// in `dead_ends`. The blocks only ever shrink `z` by dividing it, so a `z`
+    /// too big to divide down to nothing in the blocks left is a dead end too.
+    fn search(
+        &self,
+        block: usize,
+        z: i64,
+        digits: &[i64],
+        dead_ends: &mut HashSet<(usize, i64)>,
+    ) -> Option<Vec<i64>> {
+        work::step();
+        if z >= self.shrinkage[block] || dead_ends.contains(&(block, z)) {
+            return None;
+        }
+        if block == self.blocks.len() {
+            return Some(vec![]);
+        }
+        for &digit in digits {
+            let mut alu = Alu::default();
+            alu.registers[Register::Z as usize] = z;
+            if alu.run(&self.blocks[block], [digit].into_iter()).is_none() {
+                continue;
+            }
+            let next = alu.registers[Register::Z as usize];
+            if let Some(mut rest) = self.search(block + 1, next, digits, dead_ends) {
+                rest.push(digit);
+                return Some(rest);
+            }
+        }
+        dead_ends.insert((block, z));
+        None
+    }
+}
+
+/// A MONAD of the shape the puzzle's are. Half its blocks push the digit plus
+/// an offset onto `z` as a stack in base 26, and the other half pop one back
+/// off, rejecting the number unless it equals their digit less another offset.
+/// The pushes and pops pair up like brackets, and each pair's offsets add up
+/// to at most 8 either way, so some model number always gets through. There's
+/// nothing to scale.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    generate_answered(rng, scale).0
+}
+
+/// The same MONAD as [`generate`], with its largest and smallest model numbers.
+/// Each pair's popped digit is its pushed digit plus their offsets' total, so
+/// the largest number has a 9 and the smallest a 1 in whichever of the two is
+/// lower.
+pub fn generate_answered(rng: &mut Rng, _scale: usize) -> (String, Answers) {
+    let pairs = MODEL_NUMBER_LENGTH / 2;
+    let mut stack = vec![];
+    let mut pushes = 0;
+    let mut program = String::new();
+    let mut largest = [0; MODEL_NUMBER_LENGTH];
+    let mut smallest = [0; MODEL_NUMBER_LENGTH];
+    for digit in 0..MODEL_NUMBER_LENGTH {
+        let push = pushes < pairs && (stack.is_empty() || rng.chance(0.5));
+        if push {
+            pushes += 1;
+            let offset = rng.range(1..17);
+            stack.push((digit, offset));
+            // More than 9, so that the check never passes.
+            program.push_str(&block(1, rng.range(10..17), offset));
+        } else {
+            let (pushed_digit, pushed) = stack.pop().unwrap();
+            let difference = rng.range(-8..9);
+            program.push_str(&block(26, difference - pushed, rng.range(1..17)));
+            largest[pushed_digit] = 9 - difference.max(0);
+            largest[digit] = 9 + difference.min(0);
+            smallest[pushed_digit] = 1 - difference.min(0);
+            smallest[digit] = 1 + difference.max(0);
+        }
+    }
+    let number = |digits: [i64; MODEL_NUMBER_LENGTH]| digits.iter().join("");
+    let answers = Answers::new(Some(number(largest)), Some(number(smallest)));
+    (program, answers)
+}
+
+#[test]
+fn example() {
+    let binary = "\
+inp w
+add z w
+mod z 2
+div w 2
+add y w
+mod y 2
+div w 2
+add x w
+mod x 2
+div w 2
+mod w 2";
+    let program = parse::lines(binary, Instruction::parse).unwrap();
+    let mut alu = Alu::default();
+    assert_eq!(Some(()), alu.run(&program, [13].into_iter()));
+    assert_eq!([1, 1, 0, 1], alu.registers);
+    assert_eq!(None, Alu::default().run(&program, [].into_iter()));
+}
+
+#[test]
+fn finds_accepted_model_numbers() {
+    let input = generate(&mut Rng::new(24), 1);
+    let monad = Day24::parse(&input).unwrap();
+    let (Answer::Found(largest), Answer::Found(smallest)) =
+        (Day24::part1(&monad), Day24::part2(&monad))
+    else {
+        panic!("no model number found");
+    };
+    assert!(smallest <= largest);
+    for number in [largest, smallest] {
+        let digits = number
+            .to_string()
+            .bytes()
+            .map(|b| (b - b'0') as i64)
+            .collect::<Vec<_>>();
+        let mut alu = Alu::default();
+        assert_eq!(
+            Some(()),
+            alu.run(monad.blocks.iter().flatten(), digits.into_iter())
+        );
+        assert_eq!(0, alu.registers[Register::Z as usize], "{}", number);
+    }
+}
+
+#[test]
+fn search_matches_trying_every_number() {
+    let mut rng = Rng::new(24);
+    for _ in 0..50 {
+        let program = (0..3)
+            .map(|_| {
+                let divisor = *rng.pick(&[1, 2, 26]);
+                block(divisor, rng.range(-12..12), rng.range(0..10))
+            })
+            .collect::<String>();
+        let monad = Monad::from_program(&program, 3).unwrap();
+        let accepted = (111..=999)
+            .filter(|n: &u64| {
+                let digits = n
+                    .to_string()
+                    .bytes()
+                    .map(|b| (b - b'0') as i64)
+                    .collect::<Vec<_>>();
+                let mut alu = Alu::default();
+                !digits.contains(&0)
+                    && alu
+                        .run(monad.blocks.iter().flatten(), digits.into_iter())
+                        .is_some()
+                    && alu.registers[Register::Z as usize] == 0
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            (accepted.last().copied(), accepted.first().copied()),
+            (
+                monad.find_model_number([9, 8, 7, 6, 5, 4, 3, 2, 1]),
+                monad.find_model_number([1, 2, 3, 4, 5, 6, 7, 8, 9])
+            ),
+            "{}",
+            program
+        );
+    }
+}
+
+#[test]
+fn rejects_programs_unlike_monad() {
+    let program = generate(&mut Rng::new(24), 1);
+    let error = |program: &str| {
+        let error = Day24::parse(program).unwrap_err();
+        (error.line, error.message)
+    };
+    // Resetting `z` would let a number through however big `z` got.
+    let reset = program.replacen("mul z y", "mul z 0", 1);
+    assert_eq!((13, "expected `mul z y`".to_string()), error(&reset));
+    let shrinking = program.replacen("div z 1\n", "div z -1\n", 1);
+    assert_eq!(
+        (5, "expected a positive divisor".to_string()),
+        error(&shrinking)
+    );
+}
+
+#[test]
+fn reports_monad_accepting_nothing() {
+    let pushes = block(1, 10, 1).repeat(MODEL_NUMBER_LENGTH);
+    let monad = Day24::parse(&pushes).unwrap();
+    let none = Answer::Impossible(NONE_ACCEPTED.to_string());
+    assert_eq!(none, Day24::part1(&monad));
+    assert_eq!(none, Day24::part2(&monad));
+}
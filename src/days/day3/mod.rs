@@ -0,0 +1,170 @@
+use crate::error::{parse_lines, ParseError};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use std::collections::HashSet;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        let first_line = input.lines().next().unwrap_or("");
+        let width = first_line.len();
+        if width == 0 {
+            return Err(ParseError::unexpected_end(
+                input,
+                "expected a binary number",
+            ));
+        }
+        if width > u32::BITS as usize {
+            return Err(ParseError::new(
+                1,
+                first_line,
+                first_line,
+                format!("expected at most {} digits", u32::BITS),
+            ));
+        }
+        parse_lines(input, |line, s| {
+            if let Some(column) = s.find(|c| c != '0' && c != '1') {
+                Err(ParseError::at_column(
+                    line,
+                    s,
+                    column + 1,
+                    "expected a binary digit",
+                ))
+            } else if s.len() != width {
+                Err(ParseError::new(
+                    line,
+                    s,
+                    s,
+                    format!("expected {} digits", width),
+                ))
+            } else {
+                Ok(s.to_string())
+            }
+        })
+    }
+
+    fn part1(report: &Vec<String>) -> u64 {
+        let mut counts = vec![0; report[0].chars().count()];
+        report.iter().for_each(|line| {
+            line.chars()
+                .enumerate()
+                .for_each(|(pos, x)| counts[pos] += (x == '1') as usize)
+        });
+
+        let breakpoint = report.len() / 2;
+
+        let gamma: String = counts
+            .iter()
+            .map(|&count| ((count > breakpoint) as u32).to_string())
+            .collect();
+        let epsilon: String = counts
+            .iter()
+            .map(|&count| ((count < breakpoint) as u32).to_string())
+            .collect();
+
+        let rate = |bits: &str| u64::from_str_radix(bits, 2).unwrap();
+        rate(&gamma) * rate(&epsilon)
+    }
+
+    fn part2(report: &Vec<String>) -> u64 {
+        oxygen_rating(report) as u64 * carbon_rating(report) as u64
+    }
+}
+
+fn oxygen_rating(report: &[String]) -> u32 {
+    rating(
+        report,
+        |ones, total| if 2 * ones >= total { '1' } else { '0' },
+    )
+}
+
+fn carbon_rating(report: &[String]) -> u32 {
+    rating(
+        report,
+        |ones, total| if 2 * ones < total { '1' } else { '0' },
+    )
+}
+
+/// Filters the report bit by bit, keeping the numbers whose bit is the one `criteria` picks
+/// from the count of ones. A bit that every candidate shares keeps them all, and the search
+/// stops once the candidates left are all the same number.
+fn rating(report: &[String], criteria: impl Fn(usize, usize) -> char) -> u32 {
+    let mut potential_numbers: Vec<&str> = report.iter().map(String::as_str).collect();
+    let mut index_considered = 0;
+
+    while potential_numbers.iter().any(|&s| s != potential_numbers[0]) {
+        let bit = |s: &str| s.chars().nth(index_considered);
+        let ones = potential_numbers
+            .iter()
+            .filter(|&&s| bit(s) == Some('1'))
+            .count();
+
+        if ones != 0 && ones != potential_numbers.len() {
+            let keep = criteria(ones, potential_numbers.len());
+            potential_numbers.retain(|&s| bit(s) == Some(keep));
+        }
+
+        index_considered += 1;
+    }
+
+    u32::from_str_radix(potential_numbers[0], 2).unwrap()
+}
+
+/// `scale` different numbers, twelve bits wide unless that's too few to go round.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let width = 12.max(usize::BITS - scale.leading_zeros() + 1) as usize;
+    let mut seen = HashSet::new();
+    let mut input = String::new();
+    while seen.len() < scale {
+        let number = rng.below(1 << width);
+        if seen.insert(number) {
+            input.push_str(&format!("{:0width$b}\n", number, width = width));
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+#[test]
+fn example() {
+    assert_eq!((198, 230), solve::<Day3>(EXAMPLE));
+}
+
+#[test]
+fn ratings_keep_candidates_that_agree() {
+    let report = ["100".to_string(), "101".to_string()];
+    assert_eq!(5, oxygen_rating(&report));
+    assert_eq!(4, carbon_rating(&report));
+    assert_eq!(25, Day3::part2(&Day3::parse("101\n101").unwrap()));
+}
+
+#[test]
+fn rejects_numbers_wider_than_32_bits() {
+    assert!(Day3::parse(&"1".repeat(32)).is_ok());
+    let error = Day3::parse(&"1".repeat(33)).unwrap_err();
+    assert_eq!(
+        (1, "expected at most 32 digits"),
+        (error.line, error.message.as_str())
+    );
+}
@@ -0,0 +1,232 @@
+use crate::error::ParseError;
+use crate::parse::{self, check, integer, separated, words, Block};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::{Answer, Solution};
+use itertools::Itertools;
+use ndarray::Array2;
+use std::collections::BTreeSet;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Bingo;
+    type Answer1 = Answer<usize>;
+    type Answer2 = Answer<usize>;
+
+    fn parse(input: &str) -> Result<Bingo, ParseError> {
+        let blocks = parse::blocks(input);
+        let (first, cards) = blocks
+            .split_first()
+            .ok_or_else(|| ParseError::unexpected_end(input, "expected the drawn numbers"))?;
+        let drawn_numbers = first.parse_line(separated(integer("a drawn number"), ","))?;
+        let cards: Vec<BingoCard> = cards
+            .iter()
+            .map(BingoCard::from_block)
+            .collect::<Result<_, _>>()?;
+        Ok(Bingo {
+            drawn_numbers,
+            cards,
+        })
+    }
+
+    fn part1(bingo: &Bingo) -> Answer<usize> {
+        let mut _cards = bingo.cards.clone();
+        for &number in &bingo.drawn_numbers {
+            if let Some(result) = _cards
+                .iter_mut()
+                .filter_map(|card| {
+                    if card.check_number(number) {
+                        Some(card.calculate_score(number))
+                    } else {
+                        None
+                    }
+                })
+                .next()
+            {
+                return Answer::Found(result);
+            }
+        }
+
+        Answer::Impossible(NO_WINNER.to_string())
+    }
+
+    fn part2(bingo: &Bingo) -> Answer<usize> {
+        let mut _cards = bingo.cards.clone();
+        let mut most_recent_result: Option<usize> = None;
+        for &number in &bingo.drawn_numbers {
+            _cards = _cards
+                .into_iter()
+                .filter_map(|mut card| {
+                    if !card.check_number(number) {
+                        Some(card)
+                    } else {
+                        most_recent_result = Some(card.calculate_score(number));
+                        None
+                    }
+                })
+                .collect();
+        }
+
+        Answer::found_or(most_recent_result, NO_WINNER)
+    }
+}
+
+const NO_WINNER: &str = "no card completes a row or column";
+
+pub struct Bingo {
+    drawn_numbers: Vec<u8>,
+    cards: Vec<BingoCard>,
+}
+
+#[derive(Clone, Debug)]
+struct BingoCard {
+    card: Array2<u8>,
+    numbers: BTreeSet<u8>,
+    checked: Array2<bool>,
+}
+
+impl BingoCard {
+    fn from_block(block: &Block) -> Result<BingoCard, ParseError> {
+        let row = check(words(integer("a card number")), |row: &Vec<u8>| {
+            match row.len() {
+                CARD_SIZE => Ok(()),
+                _ => Err(format!("expected a row of {} numbers", CARD_SIZE)),
+            }
+        });
+        let rows = block.parse_lines(row)?;
+        if let Some(&(lineno, text)) = block.lines().get(CARD_SIZE) {
+            return Err(ParseError::new(
+                lineno,
+                text,
+                text,
+                format!("expected a blank line after {} rows", CARD_SIZE),
+            ));
+        }
+        if rows.len() < CARD_SIZE {
+            return Err(block.ended_early(format!("expected a card of {} rows", CARD_SIZE)));
+        }
+
+        let mut numbers = BTreeSet::new();
+        for (&(lineno, text), row) in block.lines().iter().zip(&rows) {
+            if let Some(number) = row.iter().find(|&&number| !numbers.insert(number)) {
+                return Err(ParseError::new(
+                    lineno,
+                    text,
+                    text,
+                    format!("repeated card number {}", number),
+                ));
+            }
+        }
+        let card = Array2::from_shape_vec([CARD_SIZE, CARD_SIZE], rows.concat()).unwrap();
+
+        Ok(BingoCard {
+            card,
+            numbers,
+            checked: Array2::<bool>::from_elem([CARD_SIZE, CARD_SIZE], false),
+        })
+    }
+
+    fn check_number(&mut self, called_number: u8) -> bool {
+        if !&self.numbers.contains(&called_number) {
+            return false;
+        }
+
+        let pos = self
+            .card
+            .iter()
+            .position(|&value| value == called_number)
+            .unwrap();
+        let (rowno, colno) = (pos / CARD_SIZE, pos % CARD_SIZE);
+        self.checked[[rowno, colno]] = true;
+
+        self.check_row(rowno) || self.check_column(colno)
+    }
+
+    fn check_row(&self, rowno: usize) -> bool {
+        self.checked.row(rowno).iter().all(|&is_checked| is_checked)
+    }
+
+    fn check_column(&self, colno: usize) -> bool {
+        self.checked
+            .column(colno)
+            .iter()
+            .all(|&is_checked| is_checked)
+    }
+
+    fn calculate_score(&self, just_called: u8) -> usize {
+        self.checked
+            .iter()
+            .zip_eq(self.card.iter())
+            .filter_map(|(&is_checked, &number)| {
+                if !is_checked {
+                    Some(number as usize)
+                } else {
+                    None
+                }
+            })
+            .sum::<usize>()
+            * just_called as usize
+    }
+}
+
+const CARD_SIZE: usize = 5;
+
+/// Every number up to 99 drawn in a random order, and `scale` cards.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut numbers = (0..100).collect_vec();
+    rng.shuffle(&mut numbers);
+    let mut input = format!("{}\n", numbers.iter().join(","));
+    for _ in 0..scale {
+        rng.shuffle(&mut numbers);
+        input.push('\n');
+        for row in numbers[..CARD_SIZE * CARD_SIZE].chunks(CARD_SIZE) {
+            input.push_str(&format!(
+                "{}\n",
+                row.iter().map(|n| format!("{:>2}", n)).join(" ")
+            ));
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+#[test]
+fn example() {
+    assert_eq!(
+        (Answer::Found(4512), Answer::Found(1924)),
+        solve::<Day4>(EXAMPLE)
+    );
+}
+
+#[test]
+fn no_answer_when_no_card_wins() {
+    let drawn = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1";
+    let no_winner = Answer::Impossible(NO_WINNER.to_string());
+    assert_eq!(
+        (no_winner.clone(), no_winner),
+        solve::<Day4>(&EXAMPLE.replace(drawn, "7,4,9,5,11,17"))
+    );
+}
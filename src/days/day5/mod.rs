@@ -0,0 +1,201 @@
+use crate::days::day5::Orientation::{Diagonal, Horizontal, Vertical};
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::image::{Image, Palette};
+use crate::parse::{self, check, integer, map, pair, Parser};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use itertools::Itertools;
+use rusttype::Point;
+use std::collections::HashMap;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = Vec<VentLine>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<VentLine>, ParseError> {
+        parse::lines(input, VentLine::parser())
+    }
+
+    fn part1(ventlines: &Vec<VentLine>) -> usize {
+        filtered_count(ventlines, |line| {
+            [Horizontal, Vertical].contains(&line.orientation())
+        })
+    }
+
+    fn part2(ventlines: &Vec<VentLine>) -> usize {
+        filtered_count(ventlines, |_| true)
+    }
+}
+
+fn filtered_count<P>(ventlines: &[VentLine], p: P) -> usize
+where
+    P: Fn(&VentLine) -> bool,
+{
+    overlaps(ventlines, p)
+        .values()
+        .filter(|&&count| count >= 2)
+        .count()
+}
+
+/// How many of the lines `p` picks out cover each point they cover.
+fn overlaps<P>(ventlines: &[VentLine], p: P) -> HashMap<Point<u16>, u8>
+where
+    P: Fn(&VentLine) -> bool,
+{
+    let mut overlaps: HashMap<Point<u16>, u8> = HashMap::new();
+
+    for line in ventlines {
+        if p(line) {
+            for point in line.all_points() {
+                let value = overlaps.entry(point).or_insert(0);
+                *value = value.saturating_add(1);
+            }
+        }
+    }
+
+    overlaps
+}
+
+/// A heatmap of how many vent lines, diagonals included, cover each point, out
+/// to the furthest any line reaches.
+pub fn picture(input: &str) -> Result<Image, ParseError> {
+    let overlaps = overlaps(&Day5::parse(input)?, |_| true);
+    let width = overlaps.keys().map(|point| point.x as usize + 1).max();
+    let height = overlaps.keys().map(|point| point.y as usize + 1).max();
+    let most = overlaps.values().copied().max().unwrap_or(0).max(1);
+    let grid = Grid::from_fn(width.unwrap_or(0), height.unwrap_or(0), |point| {
+        let point = Point {
+            x: point.x as u16,
+            y: point.y as u16,
+        };
+        overlaps.get(&point).copied().unwrap_or(0)
+    });
+    Ok(Image::new(grid.map(|&count| {
+        Palette::HEAT.at(count as f64 / most as f64)
+    })))
+}
+
+#[derive(Debug)]
+pub struct VentLine {
+    start: Point<u16>,
+    end: Point<u16>,
+}
+
+impl<'a> VentLine {
+    /// Reads `x1,y1 -> x2,y2`.
+    fn parser<'s>() -> impl Parser<'s, VentLine> {
+        let coordinate = || {
+            check(integer("a coordinate"), |&coordinate: &u16| {
+                if coordinate > MAX_COORDINATE {
+                    return Err(format!("coordinates only go up to {}", MAX_COORDINATE));
+                }
+                Ok(())
+            })
+        };
+        let point = || {
+            map(pair(coordinate(), ",", coordinate()), |(x, y)| Point {
+                x,
+                y,
+            })
+        };
+        let ventline = map(pair(point(), " -> ", point()), |(start, end)| VentLine {
+            start,
+            end,
+        });
+        check(ventline, |ventline| {
+            if ventline.orientation() == Diagonal
+                && ventline.start.x.abs_diff(ventline.end.x)
+                    != ventline.start.y.abs_diff(ventline.end.y)
+            {
+                return Err("expected a horizontal, vertical or 45 degree line".to_string());
+            }
+            Ok(())
+        })
+    }
+
+    fn orientation(&self) -> Orientation {
+        if self.start.y == self.end.y {
+            Horizontal
+        } else if self.start.x == self.end.x {
+            Vertical
+        } else {
+            Diagonal
+        }
+    }
+
+    fn all_points(&'a self) -> Box<dyn Iterator<Item = Point<u16>> + 'a> {
+        let xs: Box<dyn Iterator<Item = u16>> = if self.start.x < self.end.x {
+            Box::new(self.start.x..=self.end.x)
+        } else {
+            Box::new((self.end.x..=self.start.x).rev())
+        };
+        let ys: Box<dyn Iterator<Item = u16>> = if self.start.y < self.end.y {
+            Box::new(self.start.y..=self.end.y)
+        } else {
+            Box::new((self.end.y..=self.start.y).rev())
+        };
+
+        match self.orientation() {
+            Horizontal => Box::new(xs.map(|x| Point { x, y: self.start.y })),
+            Vertical => Box::new(ys.map(|y| Point { x: self.start.x, y })),
+            Diagonal => Box::new(xs.zip_eq(ys).map(|(x, y)| Point { x, y })),
+        }
+    }
+}
+
+/// The picture is drawn as big as the furthest line reaches, so this keeps that
+/// to a sensible size.
+const MAX_COORDINATE: u16 = 9999;
+
+#[derive(PartialEq)]
+enum Orientation {
+    Horizontal,
+    Vertical,
+    Diagonal,
+}
+
+/// `scale` lines of vents, in a 1000 by 1000 area like the real ones.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let size = 1000;
+    (0..scale)
+        .map(|_| {
+            let (x1, y1) = (rng.range(0..size), rng.range(0..size));
+            let (x2, y2) = match rng.below(3) {
+                0 => (rng.range(0..size), y1),
+                1 => (x1, rng.range(0..size)),
+                _ => {
+                    let (dx, dy) = (*rng.pick(&[-1, 1]), *rng.pick(&[-1, 1]));
+                    let room_x = if dx > 0 { size - 1 - x1 } else { x1 };
+                    let room_y = if dy > 0 { size - 1 - y1 } else { y1 };
+                    let length = rng.range(0..room_x.min(room_y) + 1);
+                    (x1 + dx * length, y1 + dy * length)
+                }
+            };
+            format!("{},{} -> {},{}\n", x1, y1, x2, y2)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+#[test]
+fn example() {
+    assert_eq!((5, 12), solve::<Day5>(EXAMPLE));
+}
@@ -0,0 +1,107 @@
+use crate::automaton;
+use crate::error::ParseError;
+use crate::parse::{self, check, integer, separated};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use array_init::array_init;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed = School;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<School, ParseError> {
+        let line = input
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::unexpected_end(input, "expected a list of fish"))?;
+        let timer = check(integer("a fish's timer"), |&age: &u8| match age as usize {
+            0..MAX_AGE => Ok(()),
+            _ => Err("timers only go up to 8".to_string()),
+        });
+        let fishes = parse::line(1, line, separated(timer, ","))?;
+
+        Ok(School::from_fishes(fishes))
+    }
+
+    fn part1(school: &School) -> usize {
+        simulate(school, 80)
+    }
+
+    fn part2(school: &School) -> usize {
+        simulate(school, 256)
+    }
+}
+
+fn simulate(initial_school: &School, steps: usize) -> usize {
+    let mut school = initial_school.clone();
+    automaton::run(&mut school, steps, School::step);
+    school.count_fish()
+}
+
+#[derive(Clone)]
+pub struct School {
+    counts: [usize; MAX_AGE],
+}
+
+impl School {
+    fn from_fishes(fishes: Vec<u8>) -> Self {
+        let mut counts = [0; MAX_AGE];
+        for fish_age in fishes {
+            counts[fish_age as usize] += 1;
+        }
+        School { counts }
+    }
+
+    fn step(&mut self) {
+        self.counts = array_init(|i| match i {
+            6 => self.counts[0] + self.counts[7],
+            8 => self.counts[0],
+            _ => self.counts[i + 1],
+        });
+    }
+
+    fn count_fish(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
+
+const MAX_AGE: usize = 9;
+
+/// `scale` fish, all partway through their first cycle.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let timers = (0..scale).map(|_| rng.range(1..6).to_string());
+    format!("{}\n", timers.collect::<Vec<_>>().join(","))
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "3,4,3,1,2";
+
+#[test]
+fn example() {
+    assert_eq!((5934, 26984457539), solve::<Day6>(EXAMPLE));
+}
+
+#[test]
+fn generated_schools_match_counting_every_fish() {
+    for seed in 0..5 {
+        let input = generate(&mut Rng::new(seed), 5);
+        let mut fishes = input
+            .trim()
+            .split(',')
+            .map(|timer| timer.parse::<u8>().unwrap())
+            .collect::<Vec<_>>();
+        for _ in 0..80 {
+            let born = fishes.iter().filter(|&&timer| timer == 0).count();
+            for timer in &mut fishes {
+                *timer = timer.checked_sub(1).unwrap_or(6);
+            }
+            fishes.extend(std::iter::repeat_n(8, born));
+        }
+        assert_eq!(fishes.len(), Day6::part1(&Day6::parse(&input).unwrap()));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::error::ParseError;
+use crate::parse::{self, check, integer, separated};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use array_init::array_init;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        let line = input
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::unexpected_end(input, "expected a list of crabs"))?;
+        let position = check(integer("a crab's position"), |&position: &usize| {
+            if position > MAX_POSITION {
+                return Err(format!("positions only go up to {}", MAX_POSITION));
+            }
+            Ok(())
+        });
+        parse::line(1, line.trim(), separated(position, ","))
+    }
+
+    fn part1(crabs: &Vec<usize>) -> usize {
+        minimise(
+            |x| calculate_cost(crabs, x, linear_metric),
+            0,
+            *crabs.iter().max().unwrap(),
+        )
+    }
+
+    fn part2(crabs: &Vec<usize>) -> usize {
+        minimise(
+            |x| calculate_cost(crabs, x, quadratic_metric),
+            0,
+            *crabs.iter().max().unwrap(),
+        )
+    }
+}
+
+fn calculate_cost<M>(crabs: &[usize], pos: usize, metric: M) -> usize
+where
+    M: Fn(usize, usize) -> usize,
+{
+    crabs.iter().map(|&crab| metric(crab, pos)).sum()
+}
+
+fn minimise<F>(f: F, start_min: usize, start_max: usize) -> usize
+where
+    F: Fn(usize) -> usize,
+{
+    let mut min = start_min;
+    let mut max = start_max;
+
+    while max - min > 5 {
+        let pivots = [
+            min,
+            (3 * min + max) / 4,
+            (min + max) / 2,
+            (min + 3 * max) / 4,
+            max,
+        ];
+        let values: [usize; 5] = array_init(|i| f(pivots[i]));
+        let (min_index, _) = values
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, &value)| value)
+            .unwrap();
+
+        min = pivots[min_index.saturating_sub(1)];
+        max = pivots[(min_index + 2).min(4)];
+    }
+
+    (min..=max).map(f).min().unwrap()
+}
+
+fn linear_metric(a: usize, b: usize) -> usize {
+    a.abs_diff(b)
+}
+
+fn quadratic_metric(a: usize, b: usize) -> usize {
+    let dist = a.abs_diff(b);
+    (dist * (dist + 1)) / 2
+}
+
+/// Far enough past the real crabs' positions, and small enough that the fuel to
+/// move any number of crabs that could be listed doesn't overflow.
+const MAX_POSITION: usize = 99_999;
+
+/// `scale` crabs, spread out like the real ones.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let positions = (0..scale).map(|_| rng.range(0..2000).to_string());
+    format!("{}\n", positions.collect::<Vec<_>>().join(","))
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "16,1,2,0,4,2,7,1,2,14";
+
+#[test]
+fn example() {
+    assert_eq!((37, 168), solve::<Day7>(EXAMPLE));
+}
+
+#[test]
+fn rejects_positions_too_far_out() {
+    for input in ["0,5000000000", "18446744073709551615", "1,100000"] {
+        assert!(Day7::parse(input).is_err(), "{}", input);
+    }
+    assert!(Day7::parse("0,99999").is_ok());
+}
@@ -0,0 +1,218 @@
+use crate::answers::Answers;
+use crate::error::ParseError;
+use crate::parse::{self, check, map, pair, word, words, Parser};
+use crate::random::Rng;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::Solution;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = Vec<Display>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Display>, ParseError> {
+        parse::lines(input, Display::parser())
+    }
+
+    fn part1(displays: &Vec<Display>) -> usize {
+        displays
+            .iter()
+            .map(|d| {
+                d.output
+                    .iter()
+                    .filter(|digit| [2, 3, 4, 7].contains(&digit.len()))
+                    .count()
+            })
+            .sum()
+    }
+
+    fn part2(displays: &Vec<Display>) -> usize {
+        displays.iter().map(|d| d.compute_output()).sum()
+    }
+}
+
+pub struct Display {
+    patterns: Vec<HashSet<char>>,
+    output: Vec<HashSet<char>>,
+}
+
+impl Display {
+    /// Reads the ten patterns, then `|` and the four output digits.
+    fn parser<'a>() -> impl Parser<'a, Display> {
+        let digits = |count: usize| {
+            let segments = check(word("a digit"), |digit: &&str| {
+                if !digit.chars().all(|c| ('a'..='g').contains(&c)) {
+                    return Err("expected segments `a` to `g`".to_string());
+                }
+                Ok(())
+            });
+            check(words(segments), move |digits: &Vec<&str>| {
+                if digits.len() != count {
+                    return Err(format!("expected {} digits", count));
+                }
+                Ok(())
+            })
+        };
+        let display = map(pair(digits(10), " | ", digits(4)), |(patterns, output)| {
+            Display {
+                patterns: patterns.into_iter().map(|s| s.chars().collect()).collect(),
+                output: output.into_iter().map(|s| s.chars().collect()).collect(),
+            }
+        });
+        check(display, Display::check_wiring)
+    }
+
+    /// Checks that the patterns are the ten digits with the wires crossed some
+    /// way, and that each output digit is one of them.
+    fn check_wiring(&self) -> Result<(), String> {
+        let digits = self.wiring().and_then(|wiring| {
+            let patterns = self.patterns.iter().map(|pattern| decode(&wiring, pattern));
+            patterns.collect::<Option<HashSet<_>>>()
+        });
+        if digits.is_none_or(|digits| digits.len() != 10) {
+            return Err(
+                "expected the patterns to be the ten digits, wired up some way".to_string(),
+            );
+        }
+        if !self
+            .output
+            .iter()
+            .all(|digit| self.patterns.contains(digit))
+        {
+            return Err("expected each output digit to be one of the patterns".to_string());
+        }
+        Ok(())
+    }
+
+    /// Which segment each wire lights. Across the ten digits, each segment is
+    /// lit a different number of times except `a` and `c`, and `d` and `g`,
+    /// and of those only `c` is in one and only `d` is in four.
+    fn wiring(&self) -> Option<HashMap<char, char>> {
+        let one = self.patterns.iter().find(|pattern| pattern.len() == 2)?;
+        let four = self.patterns.iter().find(|pattern| pattern.len() == 4)?;
+        ('a'..='g')
+            .map(|wire| {
+                let lit = self
+                    .patterns
+                    .iter()
+                    .filter(|pattern| pattern.contains(&wire))
+                    .count();
+                let segment = match (lit, one.contains(&wire), four.contains(&wire)) {
+                    (4, _, _) => 'e',
+                    (6, _, _) => 'b',
+                    (9, _, _) => 'f',
+                    (8, true, _) => 'c',
+                    (8, false, _) => 'a',
+                    (7, _, true) => 'd',
+                    (7, _, false) => 'g',
+                    _ => return None,
+                };
+                Some((wire, segment))
+            })
+            .collect()
+    }
+
+    fn compute_output(&self) -> usize {
+        let wiring = self.wiring().expect("parsing checked the wiring");
+        self.output.iter().fold(0, |number, digit| {
+            number * 10 + decode(&wiring, digit).expect("parsing checked the output")
+        })
+    }
+}
+
+/// The segments lit for each digit, when they're wired up properly.
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// The digit the wires in `lit` show, if they show one.
+fn decode(wiring: &HashMap<char, char>, lit: &HashSet<char>) -> Option<usize> {
+    let segments = lit
+        .iter()
+        .map(|wire| wiring[wire])
+        .sorted()
+        .collect::<String>();
+    DIGITS.iter().position(|&digit| digit == segments)
+}
+
+/// `scale` displays, each with its own wiring of the segments.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    generate_answered(rng, scale).0
+}
+
+/// The same displays as [`generate`], with the answers to both parts, which
+/// follow from the digits each display was made to show.
+pub fn generate_answered(rng: &mut Rng, scale: usize) -> (String, Answers) {
+    let (mut easy_digits, mut total) = (0, 0);
+    let input = (0..scale)
+        .map(|_| {
+            let mut wiring = "abcdefg".chars().collect_vec();
+            rng.shuffle(&mut wiring);
+            // Every digit once for the patterns, then any four for the output.
+            let mut digits = (0..10).collect_vec();
+            rng.shuffle(&mut digits);
+            digits.extend((0..4).map(|_| rng.below(10)));
+            easy_digits += digits[10..]
+                .iter()
+                .filter(|digit| [1, 4, 7, 8].contains(digit))
+                .count();
+            total += digits[10..].iter().fold(0, |n, digit| n * 10 + digit);
+            let mut shown = digits
+                .into_iter()
+                .map(|digit| {
+                    let mut segments = DIGITS[digit]
+                        .chars()
+                        .map(|c| wiring[(c as u8 - b'a') as usize])
+                        .collect_vec();
+                    rng.shuffle(&mut segments);
+                    segments.into_iter().collect::<String>()
+                })
+                .collect_vec();
+            let output = shown.split_off(10);
+            format!("{} | {}\n", shown.join(" "), output.join(" "))
+        })
+        .collect();
+    let answers = Answers::new(Some(easy_digits.to_string()), Some(total.to_string()));
+    (input, answers)
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+#[test]
+fn example() {
+    assert_eq!((26, 61229), solve::<Day8>(EXAMPLE));
+}
+
+#[test]
+fn rejects_patterns_that_arent_the_digits() {
+    let line = EXAMPLE.lines().next().unwrap();
+    for (broken, message) in [
+        (
+            line.replace("be ", "bf "),
+            "expected the patterns to be the ten digits, wired up some way",
+        ),
+        (
+            line.replace("| fdgacbe", "| fdg"),
+            "expected each output digit to be one of the patterns",
+        ),
+    ] {
+        let error = Day8::parse(&broken).err().unwrap();
+        assert_eq!((1, message), (error.line, error.message.as_str()));
+    }
+}
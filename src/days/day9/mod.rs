@@ -0,0 +1,193 @@
+use crate::animate::{Cell, Frame};
+use crate::colour::Colour;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::image::{Image, Palette};
+use crate::random::Rng;
+use crate::search;
+#[cfg(test)]
+use crate::solution::solve;
+use crate::solution::{Answer, Solution};
+use rusttype::Point;
+use std::collections::BinaryHeap;
+use std::iter;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Parsed = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = Answer<usize>;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse_digits(input, "a height")
+    }
+
+    fn part1(map: &Grid<u8>) -> usize {
+        map.iter()
+            .filter(|&(pos, &height)| map.neighbours4(pos).all(|n| height < map[n]))
+            .map(|(_, &height)| 1 + height as usize)
+            .sum()
+    }
+
+    fn part2(map: &Grid<u8>) -> Answer<usize> {
+        let mut basins: BinaryHeap<usize> = BinaryHeap::new();
+        let mut visited = map.map(|_| false);
+        for point in map.positions() {
+            if !visited[point] && map[point] < 9 {
+                basins.push(find_basin_size(point, map, &mut visited));
+            }
+        }
+
+        Answer::found_or(
+            basins
+                .pop()
+                .and_then(|a| basins.pop().and_then(|b| basins.pop().map(|c| a * b * c))),
+            "there are fewer than three basins",
+        )
+    }
+}
+
+/// Fills in the basins one at a time, with a frame for each.
+pub fn animate(input: &str, show: &mut dyn FnMut(Frame)) -> Result<(), ParseError> {
+    let map = Day9::parse(input)?;
+    let mut basin_count = 0;
+    label_basins(&map, |basins, size| {
+        basin_count += 1;
+        let cells = Grid::from_fn(map.width(), map.height(), |pos| {
+            Cell::new(char::from(b'0' + map[pos]), basin_colour(basins[pos]))
+        });
+        show(Frame::new(
+            format!("basin {}: {} points", basin_count, size),
+            cells,
+        ));
+    });
+    Ok(())
+}
+
+/// Every basin in its own colour, darker where it's deeper.
+pub fn picture(input: &str) -> Result<Image, ParseError> {
+    let map = Day9::parse(input)?;
+    let basins = label_basins(&map, |_, _| ());
+    Ok(Image::new(Grid::from_fn(
+        map.width(),
+        map.height(),
+        |pos| {
+            let colour = basin_colour(basins[pos]);
+            colour.towards(Colour::BLACK, (9 - map[pos]) as f64 / 18.0)
+        },
+    )))
+}
+
+/// Works out which basin each point is in, numbering them in the order they're
+/// found, and calls `filled` with the labels so far and the size of each basin
+/// as it's filled in.
+fn label_basins(
+    map: &Grid<u8>,
+    mut filled: impl FnMut(&Grid<Option<usize>>, usize),
+) -> Grid<Option<usize>> {
+    let mut visited = map.map(|_| false);
+    let mut basins = map.map(|_| None);
+    let mut basin_count = 0;
+    for point in map.positions() {
+        if !visited[point] && map[point] < 9 {
+            let size = find_basin_size(point, map, &mut visited);
+            for pos in map.positions() {
+                if visited[pos] && basins[pos].is_none() {
+                    basins[pos] = Some(basin_count);
+                }
+            }
+            basin_count += 1;
+            filled(&basins, size);
+        }
+    }
+    basins
+}
+
+fn basin_colour(basin: Option<usize>) -> Colour {
+    basin.map_or(Colour::GREY, |basin| Palette::DISTINCT.cycle(basin))
+}
+
+fn find_basin_size(start_point: Point<usize>, map: &Grid<u8>, visited: &mut Grid<bool>) -> usize {
+    let mut basin_size = 0;
+    let mut points_to_visit = vec![start_point];
+    visited[start_point] = true;
+
+    while let Some(point) = points_to_visit.pop() {
+        basin_size += 1;
+
+        for neighbour in map.neighbours4(point) {
+            if !visited[neighbour] && map[neighbour] < 9 {
+                points_to_visit.push(neighbour);
+                visited[neighbour] = true;
+            }
+        }
+    }
+
+    basin_size
+}
+
+/// A `scale` by `scale` height map, split by ridges of 9 into basins that each
+/// slope down to a low point.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    // Each basin's low point is somewhere in its own square of the map, and
+    // every position belongs to the basin with the nearest low point.
+    let spacing = (scale / 3).clamp(2, 8);
+    let squares = scale.div_ceil(spacing);
+    let low_points = Grid::from_fn(squares, squares, |square| Point {
+        x: square.x * spacing + rng.below(spacing),
+        y: square.y * spacing + rng.below(spacing),
+    });
+    let nearest = Grid::from_fn(scale, scale, |pos| {
+        let square = Point {
+            x: pos.x / spacing,
+            y: pos.y / spacing,
+        };
+        iter::once(square)
+            .chain(low_points.neighbours8(square))
+            .map(|square| (search::manhattan(pos, low_points[square]), square))
+            .min()
+            .unwrap()
+    });
+    let heights = nearest.map(|&(distance, _)| distance.min(8));
+    Grid::from_fn(scale, scale, |pos| {
+        let basin = nearest[pos].1;
+        let right = Point {
+            x: pos.x + 1,
+            ..pos
+        };
+        let below = Point {
+            y: pos.y + 1,
+            ..pos
+        };
+        let on_ridge = [right, below]
+            .iter()
+            .any(|&n| nearest.get(n).is_some_and(|&(_, other)| other != basin));
+        if on_ridge {
+            9
+        } else {
+            heights[pos]
+        }
+    })
+    .to_string()
+        + "\n"
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+#[test]
+fn example() {
+    assert_eq!((15, Answer::Found(1134)), solve::<Day9>(EXAMPLE));
+}
+
+#[test]
+fn no_answer_with_fewer_than_three_basins() {
+    let why = "there are fewer than three basins".to_string();
+    assert_eq!((2, Answer::Impossible(why)), solve::<Day9>("19\n99"));
+}
@@ -0,0 +1,402 @@
+//! The registry of every solved day.
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+use crate::animate::Animation;
+use crate::answers::Answers;
+use crate::error::ParseError;
+use crate::image::Picture;
+use crate::input::InputSource;
+use crate::random::{AnsweredGenerator, Generator};
+use crate::solution::{self, AnySolution};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::{env, io, process};
+
+/// One half of a day's puzzle.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.write_str("part1"),
+            Part::Two => f.write_str("part2"),
+        }
+    }
+}
+
+/// A registered day, with the solution that computes its answers.
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    /// The puzzle input bundled with the solution, used unless another is given.
+    /// Empty for days whose input hasn't been bundled; see [`InputSource::Bundled`].
+    pub input: &'static str,
+    /// The bundled `answers.toml`, holding the answers `input` is known to give.
+    pub answers: &'static str,
+    pub solution: &'static dyn AnySolution,
+    /// Draws the day's workings for `aoc run --animate`, if there's anything to see.
+    pub animation: Option<Animation>,
+    /// Draws a picture of the solution for `aoc run --image`.
+    pub picture: Option<Picture>,
+    /// Makes up well-formed inputs, for testing the parser against and for
+    /// `aoc gen`.
+    pub generate: Generator,
+    /// The same generator, saying what answers its input has, if it knows any.
+    pub generate_answered: Option<AnsweredGenerator>,
+}
+
+impl Day {
+    /// Entry point for the standalone `dayN` binaries, which take an optional
+    /// input path (or `-` for stdin) as their only argument.
+    pub fn run_standalone(&self) -> io::Result<()> {
+        let source = InputSource::from_arg(env::args().nth(1).as_deref());
+        let input = source.read(self)?;
+        if let Err(e) = self.print_results(&input) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        Ok(())
+    }
+
+    pub fn answers(&self) -> Result<Answers, ParseError> {
+        Answers::parse(self.answers).map_err(|e| e.in_day(self.number))
+    }
+
+    /// Prints both answers in the same format the standalone binaries always have.
+    pub fn print_results(&self, input: &str) -> Result<(), ParseError> {
+        let run = solution::run(self, input, &Part::BOTH)?;
+        for (part, answer) in run.answers {
+            println!(
+                "{} result: {}",
+                part,
+                format_answer(&answer.value.to_string())
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Where `aoc fetch` saves a day's input and `aoc submit` keeps its history, outside the
+/// source tree: `$AOC_CACHE/dayN` if that's set, or else `dayN` under `aoc/2021` in
+/// `$XDG_CACHE_HOME` or `~/.cache`.
+pub fn cache_directory(number: u8) -> Option<PathBuf> {
+    let root = env::var_os("AOC_CACHE").map(PathBuf::from).or_else(|| {
+        env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+            .map(|cache| cache.join("aoc/2021"))
+    })?;
+    Some(root.join(format!("day{}", number)))
+}
+
+/// Puts multi-line answers (like day13's folded paper) on their own lines.
+pub fn format_answer(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{}", answer)
+    } else {
+        answer.to_string()
+    }
+}
+
+pub static DAYS: [Day; 24] = [
+    Day {
+        number: 1,
+        title: "Sonar Sweep",
+        input: include_str!("day1/input.txt"),
+        answers: include_str!("day1/answers.toml"),
+        solution: &day1::Day1,
+        animation: None,
+        picture: None,
+        generate: day1::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 2,
+        title: "Dive!",
+        input: include_str!("day2/input.txt"),
+        answers: include_str!("day2/answers.toml"),
+        solution: &day2::Day2,
+        animation: None,
+        picture: None,
+        generate: day2::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 3,
+        title: "Binary Diagnostic",
+        input: include_str!("day3/input.txt"),
+        answers: include_str!("day3/answers.toml"),
+        solution: &day3::Day3,
+        animation: None,
+        picture: None,
+        generate: day3::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 4,
+        title: "Giant Squid",
+        input: include_str!("day4/input.txt"),
+        answers: include_str!("day4/answers.toml"),
+        solution: &day4::Day4,
+        animation: None,
+        picture: None,
+        generate: day4::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 5,
+        title: "Hydrothermal Venture",
+        input: include_str!("day5/input.txt"),
+        answers: include_str!("day5/answers.toml"),
+        solution: &day5::Day5,
+        animation: None,
+        picture: Some(day5::picture),
+        generate: day5::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 6,
+        title: "Lanternfish",
+        input: include_str!("day6/input.txt"),
+        answers: include_str!("day6/answers.toml"),
+        solution: &day6::Day6,
+        animation: None,
+        picture: None,
+        generate: day6::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 7,
+        title: "The Treachery of Whales",
+        input: include_str!("day7/input.txt"),
+        answers: include_str!("day7/answers.toml"),
+        solution: &day7::Day7,
+        animation: None,
+        picture: None,
+        generate: day7::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 8,
+        title: "Seven Segment Search",
+        input: include_str!("day8/input.txt"),
+        answers: include_str!("day8/answers.toml"),
+        solution: &day8::Day8,
+        animation: None,
+        picture: None,
+        generate: day8::generate,
+        generate_answered: Some(day8::generate_answered),
+    },
+    Day {
+        number: 9,
+        title: "Smoke Basin",
+        input: include_str!("day9/input.txt"),
+        answers: include_str!("day9/answers.toml"),
+        solution: &day9::Day9,
+        animation: Some(day9::animate),
+        picture: Some(day9::picture),
+        generate: day9::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 10,
+        title: "Syntax Scoring",
+        input: include_str!("day10/input.txt"),
+        answers: include_str!("day10/answers.toml"),
+        solution: &day10::Day10,
+        animation: None,
+        picture: None,
+        generate: day10::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 11,
+        title: "Dumbo Octopus",
+        input: include_str!("day11/input.txt"),
+        answers: include_str!("day11/answers.toml"),
+        solution: &day11::Day11,
+        animation: Some(day11::animate),
+        picture: None,
+        generate: day11::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 12,
+        title: "Passage Pathing",
+        input: include_str!("day12/input.txt"),
+        answers: include_str!("day12/answers.toml"),
+        solution: &day12::Day12,
+        animation: None,
+        picture: None,
+        generate: day12::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 13,
+        title: "Transparent Origami",
+        input: include_str!("day13/input.txt"),
+        answers: include_str!("day13/answers.toml"),
+        solution: &day13::Day13,
+        animation: Some(day13::animate),
+        picture: Some(day13::picture),
+        generate: day13::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 14,
+        title: "Extended Polymerization",
+        input: include_str!("day14/input.txt"),
+        answers: include_str!("day14/answers.toml"),
+        solution: &day14::Day14,
+        animation: None,
+        picture: None,
+        generate: day14::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 15,
+        title: "Chiton",
+        input: include_str!("day15/input.txt"),
+        answers: include_str!("day15/answers.toml"),
+        solution: &day15::Day15,
+        animation: Some(day15::animate),
+        picture: Some(day15::picture),
+        generate: day15::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 16,
+        title: "Packet Decoder",
+        input: include_str!("day16/input.txt"),
+        answers: include_str!("day16/answers.toml"),
+        solution: &day16::Day16,
+        animation: None,
+        picture: None,
+        generate: day16::generate,
+        generate_answered: Some(day16::generate_answered),
+    },
+    Day {
+        number: 17,
+        title: "Trick Shot",
+        input: include_str!("day17/input.txt"),
+        answers: include_str!("day17/answers.toml"),
+        solution: &day17::Day17,
+        animation: None,
+        picture: None,
+        generate: day17::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 18,
+        title: "Snailfish",
+        input: include_str!("day18/input.txt"),
+        answers: include_str!("day18/answers.toml"),
+        solution: &day18::Day18,
+        animation: None,
+        picture: None,
+        generate: day18::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 19,
+        title: "Beacon Scanner",
+        input: include_str!("day19/input.txt"),
+        answers: include_str!("day19/answers.toml"),
+        solution: &day19::Day19,
+        animation: None,
+        picture: None,
+        generate: day19::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 20,
+        title: "Trench Map",
+        input: include_str!("day20/input.txt"),
+        answers: include_str!("day20/answers.toml"),
+        solution: &day20::Day20,
+        animation: None,
+        picture: None,
+        generate: day20::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 21,
+        title: "Dirac Dice",
+        input: include_str!("day21/input.txt"),
+        answers: include_str!("day21/answers.toml"),
+        solution: &day21::Day21,
+        animation: None,
+        picture: None,
+        generate: day21::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 22,
+        title: "Reactor Reboot",
+        input: include_str!("day22/input.txt"),
+        answers: include_str!("day22/answers.toml"),
+        solution: &day22::Day22,
+        animation: None,
+        picture: None,
+        generate: day22::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 23,
+        title: "Amphipod",
+        input: include_str!("day23/input.txt"),
+        answers: include_str!("day23/answers.toml"),
+        solution: &day23::Day23,
+        animation: None,
+        picture: None,
+        generate: day23::generate,
+        generate_answered: None,
+    },
+    Day {
+        number: 24,
+        title: "Arithmetic Logic Unit",
+        input: include_str!("day24/input.txt"),
+        answers: include_str!("day24/answers.toml"),
+        solution: &day24::Day24,
+        animation: None,
+        picture: None,
+        generate: day24::generate,
+        generate_answered: Some(day24::generate_answered),
+    },
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,114 @@
+//! The error returned when a day's puzzle input is malformed.
+
+use std::fmt::{Display, Formatter};
+
+/// Where and why a day's input failed to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// Filled in by the runner, since the parsers themselves don't know which day they are.
+    pub day: Option<u8>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The whole of the offending line.
+    pub line_text: String,
+    /// The part of the line that couldn't be parsed.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Builds an error pointing at `text`. If `text` is a slice of `line_text`
+    /// the column is worked out from where it sits; otherwise it's the line's end.
+    pub fn new(line: usize, line_text: &str, text: &str, message: impl Into<String>) -> Self {
+        let start = line_text.as_ptr() as usize;
+        let offset = (text.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset + text.len() <= line_text.len())
+            .unwrap_or(line_text.len());
+        ParseError {
+            day: None,
+            line,
+            column: line_text[..offset].chars().count() + 1,
+            line_text: line_text.to_string(),
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// An error at a known column, for parsers that walk a line character by character.
+    pub fn at_column(
+        line: usize,
+        line_text: &str,
+        column: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        ParseError {
+            day: None,
+            line,
+            column,
+            line_text: line_text.to_string(),
+            text: line_text.chars().skip(column - 1).take(1).collect(),
+            message: message.into(),
+        }
+    }
+
+    /// An error for input that stopped before it should have.
+    pub fn unexpected_end(input: &str, message: impl Into<String>) -> Self {
+        let line_text = input.lines().last().unwrap_or("");
+        ParseError {
+            day: None,
+            line: input.lines().count().max(1),
+            column: line_text.chars().count() + 1,
+            line_text: line_text.to_string(),
+            text: String::new(),
+            message: message.into(),
+        }
+    }
+
+    pub fn in_day(self, day: u8) -> Self {
+        ParseError {
+            day: Some(day),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day{} ", day)?;
+        }
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        let gutter = self.line.to_string().len();
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.line_text)?;
+        write!(
+            f,
+            "{:gutter$} | {:pad$}{}",
+            "",
+            "",
+            "^".repeat(self.text.chars().count().max(1)),
+            pad = self.column - 1
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses every line of `input` with `f`, which is given the 1-based line number along with the line.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    f: impl Fn(usize, &'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(i + 1, line))
+        .collect()
+}
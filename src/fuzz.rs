@@ -0,0 +1,257 @@
+//! Randomised tests of every day: inputs that are valid have to parse and solve
+//! within a set amount of work, to the answers they were built to have where
+//! those are known, and print back as they were written where they can be.
+//! Mutated ones have to be rejected with an error rather than a panic, or else
+//! happen to still be valid and solve. Ones that are certainly broken have to be
+//! rejected on the right line.
+
+use crate::answers::Answers;
+use crate::days::day18::Day18;
+use crate::days::day23::Day23;
+use crate::days::{self, Day, Part, DAYS};
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
+use crate::work;
+use std::fmt::Debug;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Characters that mean something to at least one day's parser, plus a few that
+/// don't, to splice into inputs.
+const INTERESTING: &[char] = &[
+    '0', '1', '5', '9', ',', '-', '>', ' ', '\n', '|', '[', ']', '(', '<', '=', '.', 'x', 'y', 'a',
+    'F', '#', 'é', '\t',
+];
+
+/// Makes a single random change to `input`, and says what it was.
+fn mutate(rng: &mut Rng, input: &mut String) -> String {
+    let at = char_boundary(input, rng.below(input.len() + 1));
+    match rng.below(6) {
+        0 => {
+            let c = *rng.pick(INTERESTING);
+            input.insert(at, c);
+            format!("inserted {:?} at byte {}", c, at)
+        }
+        1 if at < input.len() => {
+            let c = *rng.pick(INTERESTING);
+            let old = input[at..].chars().next().unwrap();
+            input.replace_range(at..at + old.len_utf8(), &c.to_string());
+            format!("replaced {:?} at byte {} with {:?}", old, at, c)
+        }
+        2 => {
+            let end = char_boundary(input, at + rng.below(8));
+            input.replace_range(at..end, "");
+            format!("deleted bytes {}..{}", at, end)
+        }
+        3 => {
+            input.truncate(at);
+            format!("truncated to {} bytes", at)
+        }
+        4 => {
+            let digits = rng.range(1..25) as usize;
+            input.insert_str(at, &"9".repeat(digits));
+            format!("inserted {} nines at byte {}", digits, at)
+        }
+        _ => {
+            // Repeat a line, which is how a lot of structure gets broken.
+            let line_start = input[..at].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = input[at..].find('\n').map_or(input.len(), |i| at + i);
+            let line = format!("{}\n", &input[line_start..line_end]);
+            input.insert_str(line_start, &line);
+            format!("repeated the line at byte {}", line_start)
+        }
+    }
+}
+
+/// The nearest char boundary at or after `at`.
+fn char_boundary(s: &str, at: usize) -> usize {
+    (at.min(s.len())..=s.len())
+        .find(|&i| s.is_char_boundary(i))
+        .unwrap()
+}
+
+/// Parses `input`, turning a panic into an explanation of how the input was made.
+fn parse_without_panicking(day: &Day, input: &str, how: &str) {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| day.solution.parse_any(input)));
+    if result.is_err() {
+        panic!(
+            "day{}'s parser panicked on {}:\n{}",
+            day.number,
+            how,
+            input.chars().take(2000).collect::<String>()
+        );
+    }
+}
+
+/// How many steps through the shared automaton and search helpers the two
+/// parts get between them on a made-up input, which is far more than any of
+/// them needs: day24's search, the longest, takes about 6 million.
+const WORK_LIMIT: usize = 20_000_000;
+
+/// Solves both parts of `input`, if it parses, turning a panic or a run past
+/// [`WORK_LIMIT`] into an explanation of how the input was made.
+fn solve_without_panicking(day: &Day, input: &str, how: &str) -> Option<[Answer<String>; 2]> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        work::limited(WORK_LIMIT, || {
+            let parsed = day.solution.parse_any(input).ok()?;
+            Some(Part::BOTH.map(|part| day.solution.solve_any(parsed.as_ref(), part)))
+        })
+    }));
+    let failure = match result {
+        Ok(Some(answers)) => return answers,
+        Ok(None) => format!("took more than {} steps", WORK_LIMIT),
+        Err(_) => "panicked".to_string(),
+    };
+    panic!(
+        "day{} {} on {}:\n{}",
+        day.number,
+        failure,
+        how,
+        input.chars().take(2000).collect::<String>()
+    );
+}
+
+/// Checks that every day's inputs generated from `seeds` at each of `scales`
+/// parse and solve, to the answers they were built to have where those are known.
+fn check_generated_inputs(seeds: Range<u64>, scales: &[usize]) {
+    for day in &DAYS {
+        for seed in seeds.clone() {
+            let scale = scales[seed as usize % scales.len()];
+            let mut rng = Rng::new(seed);
+            let (input, answers) = match day.generate_answered {
+                Some(generate) => generate(&mut rng, scale),
+                None => ((day.generate)(&mut rng, scale), Answers::default()),
+            };
+            if let Err(e) = day.solution.parse_any(&input) {
+                panic!(
+                    "day{}'s generated input (seed {}, scale {}) didn't parse: {}",
+                    day.number, seed, scale, e
+                );
+            }
+            let how = format!("the input generated from seed {} at scale {}", seed, scale);
+            let solved = solve_without_panicking(day, &input, &how).unwrap();
+            for (part, actual) in Part::BOTH.into_iter().zip(solved) {
+                if let Some(expected) = answers.get(part) {
+                    assert_eq!(
+                        Answer::Found(expected.to_string()),
+                        actual,
+                        "day{} {} on {}",
+                        day.number,
+                        part,
+                        how
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Checks that what `S` parses from day `number`'s generated inputs prints, by
+/// way of `print`, as the text it was parsed from, and so parses back the same.
+fn check_round_trip<S: Solution>(number: u8, print: impl Fn(&S::Parsed) -> String)
+where
+    S::Parsed: Debug + PartialEq,
+{
+    let day = days::find(number).unwrap();
+    for seed in 0..10 {
+        let input = (day.generate)(&mut Rng::new(seed), 1 + seed as usize % 5);
+        let parsed = S::parse(&input).unwrap();
+        let printed = print(&parsed);
+        assert_eq!(input, printed, "day{} on seed {}", number, seed);
+        assert_eq!(
+            parsed,
+            S::parse(&printed).unwrap(),
+            "day{} on seed {}",
+            number,
+            seed
+        );
+    }
+}
+
+#[test]
+fn generated_inputs_solve_as_built() {
+    check_generated_inputs(0..10, &[1, 2, 3]);
+}
+
+/// The same as [`generated_inputs_solve_as_built`] from more seeds at bigger
+/// scales, which is too slow to run every time. Run with `cargo test -- --ignored`.
+#[test]
+#[ignore]
+fn bigger_generated_inputs_solve_as_built() {
+    check_generated_inputs(10..30, &[3, 5, 8]);
+}
+
+#[test]
+fn generated_inputs_print_as_they_parse() {
+    check_round_trip::<Day18>(18, |numbers| {
+        numbers
+            .iter()
+            .map(|number| format!("{}\n", number))
+            .collect()
+    });
+    check_round_trip::<Day23>(23, |burrow| burrow.to_string());
+}
+
+#[test]
+fn mutated_inputs_are_rejected_without_panicking() {
+    let mut rng = Rng::new(2021);
+    for day in &DAYS {
+        for round in 0..100 {
+            // Half the time start from the real input, and half from a small
+            // generated one, where a mutation is more likely to hit something
+            // structural. Only the small ones are quick enough to solve as well.
+            let bundled = round % 2 == 0;
+            let (mut input, source) = if bundled {
+                (day.input.to_string(), "the bundled input".to_string())
+            } else {
+                let seed = rng.next_u64();
+                let input = (day.generate)(&mut Rng::new(seed), 3);
+                (input, format!("the input generated from seed {}", seed))
+            };
+            let mutations = (0..rng.range(1..4))
+                .map(|_| mutate(&mut rng, &mut input))
+                .collect::<Vec<_>>();
+            let how = format!("{}, {}", source, mutations.join(", "));
+            if bundled {
+                parse_without_panicking(day, &input, &how);
+            } else {
+                solve_without_panicking(day, &input, &how);
+            }
+        }
+        parse_without_panicking(day, "", "an empty input");
+        parse_without_panicking(day, "\n", "a blank line");
+    }
+}
+
+#[test]
+fn corrupted_numbers_are_rejected_on_their_line() {
+    let mut rng = Rng::new(17);
+    for day in &DAYS {
+        for seed in 0..10 {
+            let mut input = (day.generate)(&mut Rng::new(seed), 3);
+            let digits = input
+                .char_indices()
+                .filter(|(_, c)| c.is_ascii_digit())
+                .map(|(at, _)| at)
+                .collect::<Vec<_>>();
+            // Some days' inputs have no numbers in them.
+            if digits.is_empty() {
+                continue;
+            }
+            let at = *rng.pick(&digits);
+            input.replace_range(at..at + 1, "#");
+            let line = input[..at].matches('\n').count() + 1;
+            match day.solution.parse_any(&input) {
+                Ok(_) => panic!(
+                    "day{} accepted a `#` at byte {} of the input generated from seed {}",
+                    day.number, at, seed
+                ),
+                Err(e) => assert_eq!(
+                    line, e.line,
+                    "day{} put the `#` on the wrong line ({}) in the input generated from seed {}",
+                    day.number, e, seed
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! A rectangular grid of cells, the shape most of the puzzles' maps take.
+
+use crate::error::{parse_lines, ParseError};
+use ndarray::Array2;
+use rusttype::Point;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// Offsets to the cells sharing an edge, clockwise from above.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to the cells sharing an edge or a corner, clockwise from above.
+const SURROUNDING: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A `width` by `height` grid, addressed by `Point { x, y }` with the origin at the top left.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    // Indexed `[[y, x]]`, so the rows are contiguous.
+    cells: Array2<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point<usize>) -> T) -> Self {
+        Grid {
+            cells: Array2::from_shape_fn([height, width], |(y, x)| f(Point { x, y })),
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: Array2::from_elem([height, width], value),
+        }
+    }
+
+    /// Parses one row per line, with `f` turning each character into a cell. Every
+    /// row has to be as wide as the first, and a character `f` rejects is reported
+    /// as not being the `expected` kind of cell.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        f: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        let rows = parse_lines(input, |lineno, line| {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(column, c)| {
+                    f(c).ok_or_else(|| {
+                        ParseError::at_column(
+                            lineno,
+                            line,
+                            column + 1,
+                            format!("expected {}", expected),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if row.len() != width {
+                return Err(ParseError::new(
+                    lineno,
+                    line,
+                    line,
+                    format!("expected a row {} wide, like the first", width),
+                ));
+            }
+            Ok(row)
+        })?;
+        if width == 0 {
+            return Err(ParseError::unexpected_end(input, "expected a grid"));
+        }
+        let height = rows.len();
+        let cells = Array2::from_shape_vec([height, width], rows.into_iter().flatten().collect());
+        Ok(Grid {
+            cells: cells.unwrap(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    pub fn contains(&self, pos: Point<usize>) -> bool {
+        pos.x < self.width() && pos.y < self.height()
+    }
+
+    pub fn get(&self, pos: Point<usize>) -> Option<&T> {
+        self.cells.get([pos.y, pos.x])
+    }
+
+    pub fn get_mut(&mut self, pos: Point<usize>) -> Option<&mut T> {
+        self.cells.get_mut([pos.y, pos.x])
+    }
+
+    /// Every position in the grid, a row at a time.
+    pub fn positions(&self) -> impl Iterator<Item = Point<usize>> {
+        let width = self.width();
+        (0..self.height()).flat_map(move |y| (0..width).map(move |x| Point { x, y }))
+    }
+
+    /// Every cell along with its position, a row at a time.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Each row in turn, from the top.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells
+            .rows()
+            .into_iter()
+            .map(|row| row.to_slice().expect("rows are contiguous"))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.cells.iter_mut()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.map(f),
+        }
+    }
+
+    /// The up to four positions sharing an edge with `pos`.
+    pub fn neighbours4(&self, pos: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        self.offsets(pos, &ORTHOGONAL)
+    }
+
+    /// The up to eight positions sharing an edge or a corner with `pos`.
+    pub fn neighbours8(&self, pos: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        self.offsets(pos, &SURROUNDING)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        pos: Point<usize>,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point<usize>> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(dx, dy)| {
+                Some(Point {
+                    x: pos.x.checked_add_signed(dx)?,
+                    y: pos.y.checked_add_signed(dy)?,
+                })
+            })
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+}
+
+impl<T: From<u8>> Grid<T> {
+    /// Parses a map of single decimal digits, such as heights or energy levels.
+    pub fn parse_digits(input: &str, expected: &str) -> Result<Self, ParseError> {
+        Self::parse(input, expected, |c| {
+            c.to_digit(10).map(|digit| T::from(digit as u8))
+        })
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<usize>) -> &T {
+        &self.cells[[pos.y, pos.x]]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Point<usize>) -> &mut T {
+        &mut self.cells[[pos.y, pos.x]]
+    }
+}
+
+/// Writes each row on its own line, with the cells run together.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn neighbours_stay_in_bounds() {
+    let grid = Grid::filled(3, 2, 0);
+    let corner = Point { x: 0, y: 0 };
+    let edge = Point { x: 1, y: 1 };
+
+    assert_eq!(
+        vec![Point { x: 1, y: 0 }, Point { x: 0, y: 1 }],
+        grid.neighbours4(corner).collect::<Vec<_>>()
+    );
+    assert_eq!(3, grid.neighbours8(corner).count());
+    assert_eq!(3, grid.neighbours4(edge).count());
+    assert_eq!(5, grid.neighbours8(edge).count());
+}
+
+#[test]
+fn parse_and_display_round_trip() {
+    let input = "123\n456";
+    let grid = Grid::<u8>::parse_digits(input, "a digit").unwrap();
+
+    assert_eq!((3, 2), (grid.width(), grid.height()));
+    assert_eq!(6, grid[Point { x: 2, y: 1 }]);
+    assert_eq!(input, grid.to_string());
+
+    let short_row = Grid::<u8>::parse_digits("12\n3", "a digit").unwrap_err();
+    assert_eq!(2, short_row.line);
+    let bad_cell = Grid::<u8>::parse_digits("12\n3x", "a digit").unwrap_err();
+    assert_eq!((2, 2), (bad_cell.line, bad_cell.column));
+
+    let mut rng = crate::random::Rng::new(9);
+    for _ in 0..20 {
+        let (width, height) = (rng.range(1..12) as usize, rng.range(1..12) as usize);
+        let grid = Grid::from_fn(width, height, |_| rng.below(10) as u8);
+        assert_eq!(
+            Ok(&grid),
+            Grid::parse_digits(&grid.to_string(), "a digit").as_ref()
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! A bare-bones HTTP client, since we only ever make a handful of requests.
+//!
+//! Plain `http://` URLs are spoken to directly over a TCP socket, which is all a
+//! local stand-in server needs. `https://` needs TLS, so those requests are
+//! handed to the system's `curl` instead.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Makes a request with the given extra headers, and a form-encoded `body` if there is one.
+pub fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    if let Some(rest) = url.strip_prefix("http://") {
+        request_plain(method, rest, headers, body)
+    } else if url.starts_with("https://") {
+        request_with_curl(method, url, headers, body)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("expected an http:// or https:// URL, got `{}`", url),
+        ))
+    }
+}
+
+/// Sends an HTTP/1.0 request, so that the response comes back in one piece and
+/// the server closes the connection once it's sent.
+fn request_plain(
+    method: &str,
+    host_and_path: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    let (host, path) = match host_and_path.find('/') {
+        Some(slash) => host_and_path.split_at(slash),
+        None => (host_and_path, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let mut request = format!("{} {} HTTP/1.0\r\nHost: {}\r\n", method, path, host);
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    if let Some(body) = body {
+        request.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(body.unwrap_or(""));
+
+    let mut stream = TcpStream::connect(address)?;
+    stream.write_all(request.as_bytes())?;
+
+    let mut reader = BufReader::new(stream);
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| invalid_response(&status_line))?;
+    // Skip the headers; nothing we ask for needs them.
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+    }
+    let mut body = String::new();
+    reader.read_to_string(&mut body)?;
+
+    Ok(Response { status, body })
+}
+
+fn request_with_curl(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    let (mut command, config) = curl_command(method, url, headers, body);
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Dropping stdin once it's written closes it, so curl knows the config has ended.
+    child.stdin.take().unwrap().write_all(config.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "curl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or_else(|| invalid_response(&stdout))?;
+    let status = status.parse().map_err(|_| invalid_response(status))?;
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// The curl command for a request, and the config to feed it on stdin. The headers go in the
+/// config rather than on the command line, where any user on the machine could read the
+/// session cookie from the process list.
+fn curl_command(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> (Command, String) {
+    let mut command = Command::new("curl");
+    command.args(["--silent", "--show-error", "--config", "-"]);
+    command.args(["--request", method]);
+    if let Some(body) = body {
+        command.arg("--data-raw").arg(body);
+    }
+    // Put the status on a line of its own after the body.
+    command.args(["--write-out", "\n%{http_code}", url]);
+
+    let config = headers
+        .iter()
+        .map(|(name, value)| {
+            let header = format!("{}: {}", name, value);
+            format!(
+                "header = \"{}\"\n",
+                header.replace('\\', "\\\\").replace('"', "\\\"")
+            )
+        })
+        .collect();
+    (command, config)
+}
+
+fn invalid_response(text: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("couldn't understand the response `{}`", text.trim()),
+    )
+}
+
+/// A stand-in server for tests, listening on a local port. It answers each
+/// request it's sent with the next of `responses` (a status and a body), and
+/// hands back the raw requests once they've all been used up.
+#[cfg(test)]
+pub(crate) fn stand_in_server(
+    responses: Vec<(u16, &'static str)>,
+) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        responses
+            .into_iter()
+            .map(|(status, body)| {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.strip_prefix("Content-Length: ") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+                }
+                let mut body_bytes = vec![0; content_length];
+                reader.read_exact(&mut body_bytes).unwrap();
+                request.push_str(&String::from_utf8(body_bytes).unwrap());
+
+                write!(stream, "HTTP/1.0 {} Stand-in\r\n\r\n{}", status, body).unwrap();
+                request
+            })
+            .collect()
+    });
+    (base_url, server)
+}
+
+#[test]
+fn plain_requests() {
+    let (base_url, server) = stand_in_server(vec![(200, "hello"), (404, "")]);
+
+    let response = request("GET", &format!("{}/greeting", base_url), &[], None).unwrap();
+    assert_eq!((200, "hello"), (response.status, response.body.as_str()));
+    let response = request(
+        "POST",
+        &format!("{}/missing", base_url),
+        &[("Cookie", "session=abc")],
+        Some("level=1"),
+    )
+    .unwrap();
+    assert_eq!(404, response.status);
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /greeting HTTP/1.0\r\n"));
+    assert!(requests[1].starts_with("POST /missing HTTP/1.0\r\n"));
+    assert!(requests[1].contains("Cookie: session=abc\r\n"));
+    assert!(requests[1].ends_with("\r\n\r\nlevel=1"));
+}
+
+#[test]
+fn curl_gets_headers_from_stdin() {
+    let headers = [("Cookie", "session=secret\"token")];
+    let (command, config) = curl_command("GET", "https://example.com/", &headers, None);
+    assert!(command
+        .get_args()
+        .all(|arg| !arg.to_string_lossy().contains("secret")));
+    assert_eq!("header = \"Cookie: session=secret\\\"token\"\n", config);
+
+    // The rest needs curl itself.
+    if Command::new("curl").arg("--version").output().is_err() {
+        eprintln!("skipping the round trip through curl, which isn't installed");
+        return;
+    }
+    let (base_url, server) = stand_in_server(vec![(200, "hello")]);
+    let response = request_with_curl("GET", &base_url, &headers, None).unwrap();
+    assert_eq!((200, "hello"), (response.status, response.body.as_str()));
+    let requests = server.join().unwrap();
+    assert!(requests[0].contains("Cookie: session=secret\"token\r\n"));
+}
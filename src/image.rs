@@ -0,0 +1,221 @@
+//! Writing pictures of the puzzles' grids to image files, with no dependencies:
+//! binary PPM, which almost anything can convert, or PNG for putting straight on
+//! a web page. The PNG writer doesn't compress: it wraps the pixels in deflate's
+//! stored blocks, which keeps it short but makes the files about as big as the
+//! PPM ones.
+
+use crate::colour::Colour;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use rusttype::Point;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// A day's picture of its solution, drawn from `input`.
+pub type Picture = fn(input: &str) -> Result<Image, ParseError>;
+
+/// A run of colours to pick from, either blended smoothly for a quantity or
+/// taken in turn to tell things apart.
+#[derive(Clone, Copy, Debug)]
+pub struct Palette {
+    stops: &'static [Colour],
+}
+
+impl Palette {
+    /// Black through red and yellow to white, for how much of something there is.
+    pub const HEAT: Palette = Palette {
+        stops: &[Colour::BLACK, Colour::RED, Colour::YELLOW, Colour::WHITE],
+    };
+
+    /// Blue to red, for low to high.
+    pub const COOL_TO_WARM: Palette = Palette {
+        stops: &[Colour::BLUE, Colour::rgb(150, 150, 150), Colour::RED],
+    };
+
+    /// Colours that stand apart from each other, for labelling regions.
+    pub const DISTINCT: Palette = Palette {
+        stops: &[
+            Colour::RED,
+            Colour::YELLOW,
+            Colour::BLUE,
+            Colour::rgb(60, 180, 75),
+            Colour::rgb(170, 80, 200),
+            Colour::rgb(245, 130, 48),
+        ],
+    };
+
+    /// The colour `fraction` of the way along the palette, blending between stops.
+    pub fn at(&self, fraction: f64) -> Colour {
+        let position = fraction.clamp(0.0, 1.0) * (self.stops.len() - 1) as f64;
+        let stop = (position as usize).min(self.stops.len() - 2);
+        self.stops[stop].towards(self.stops[stop + 1], position - stop as f64)
+    }
+
+    /// The `index`th colour, going round again once they run out.
+    pub fn cycle(&self, index: usize) -> Colour {
+        self.stops[index % self.stops.len()]
+    }
+}
+
+/// A picture, one pixel per grid cell.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    pixels: Grid<Colour>,
+}
+
+impl Image {
+    pub fn new(pixels: Grid<Colour>) -> Self {
+        Image { pixels }
+    }
+
+    /// Blows each pixel up into a `factor` by `factor` square, since some grids
+    /// are too small to make out otherwise.
+    pub fn scaled(&self, factor: usize) -> Image {
+        let (width, height) = (self.pixels.width(), self.pixels.height());
+        Image::new(Grid::from_fn(width * factor, height * factor, |pos| {
+            self.pixels[Point {
+                x: pos.x / factor,
+                y: pos.y / factor,
+            }]
+        }))
+    }
+
+    /// Saves as PNG if `path` ends in `.png`, or PPM otherwise.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+        {
+            self.write_png(&mut out)?;
+        } else {
+            self.write_ppm(&mut out)?;
+        }
+        out.flush()
+    }
+
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        write!(
+            out,
+            "P6\n{} {}\n255\n",
+            self.pixels.width(),
+            self.pixels.height()
+        )?;
+        out.write_all(&self.rgb_bytes(false))
+    }
+
+    /// Writes an uncompressed PNG, its pixel data in deflate stored blocks.
+    pub fn write_png(&self, mut out: impl Write) -> io::Result<()> {
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut header = vec![];
+        header.extend((self.pixels.width() as u32).to_be_bytes());
+        header.extend((self.pixels.height() as u32).to_be_bytes());
+        // 8 bits per channel, RGB, and the standard compression, filtering and
+        // (lack of) interlacing.
+        header.extend([8, 2, 0, 0, 0]);
+        write_chunk(&mut out, b"IHDR", &header)?;
+        write_chunk(&mut out, b"IDAT", &zlib_stored(&self.rgb_bytes(true)))?;
+        write_chunk(&mut out, b"IEND", &[])
+    }
+
+    /// The pixels a row at a time, each row starting with PNG's "no filter" byte
+    /// if `filter_bytes` is set.
+    fn rgb_bytes(&self, filter_bytes: bool) -> Vec<u8> {
+        let mut bytes = vec![];
+        for row in self.pixels.rows() {
+            if filter_bytes {
+                bytes.push(0);
+            }
+            bytes.extend(row.iter().flat_map(|colour| [colour.r, colour.g, colour.b]));
+        }
+        bytes
+    }
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data));
+    out.write_all(&crc.to_be_bytes())
+}
+
+/// Wraps `data` up as a zlib stream without compressing it, in deflate's
+/// "stored" blocks of at most 65535 bytes.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let length = block.len() as u16;
+        stream.push(last as u8);
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[test]
+fn palettes() {
+    assert_eq!(Colour::BLACK, Palette::HEAT.at(0.0));
+    assert_eq!(Colour::WHITE, Palette::HEAT.at(1.0));
+    assert_eq!(Colour::RED, Palette::HEAT.at(1.0 / 3.0));
+    assert_eq!(Colour::rgb(110, 25, 24), Palette::HEAT.at(1.0 / 6.0));
+    assert_eq!(Palette::DISTINCT.cycle(1), Palette::DISTINCT.cycle(7));
+}
+
+#[test]
+fn image_files() {
+    let image = Image::new(Grid::from_fn(2, 1, |pos| match pos.x {
+        0 => Colour::RED,
+        _ => Colour::WHITE,
+    }))
+    .scaled(2);
+
+    let mut ppm = vec![];
+    image.write_ppm(&mut ppm).unwrap();
+    assert!(ppm.starts_with(b"P6\n4 2\n255\n"));
+    assert_eq!(11 + 4 * 2 * 3, ppm.len());
+    assert_eq!([220, 50, 47, 220, 50, 47, 255, 255, 255], ppm[11..20]);
+
+    let mut png = vec![];
+    image.write_png(&mut png).unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR"));
+    assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    assert_eq!(
+        0x414fa339,
+        crc32(b"The quick brown fox jumps over the lazy dog")
+    );
+    assert_eq!(0x11e60398, adler32(b"Wikipedia"));
+}
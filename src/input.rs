@@ -0,0 +1,91 @@
+//! Loading puzzle input at runtime.
+
+use crate::days::{self, Day};
+use std::borrow::Cow;
+use std::io::Read;
+use std::path::PathBuf;
+use std::{fs, io};
+
+/// Where to read a day's puzzle input from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+    /// The `input.txt` that `aoc fetch` saved in the day's cache directory or, if there
+    /// isn't one, the one compiled in next to the day's solution. Empty if there's neither.
+    Bundled,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Interprets a command-line argument: nothing means the fetched or bundled input and
+    /// `-` means stdin; anything else is a file path.
+    pub fn from_arg(arg: Option<&str>) -> Self {
+        match arg {
+            None => InputSource::Bundled,
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self, day: &Day) -> io::Result<Cow<'static, str>> {
+        match self {
+            InputSource::Bundled => cached_or_bundled(day, days::cache_directory(day.number)),
+            InputSource::File(path) => fs::read_to_string(path).map(Cow::Owned),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(Cow::Owned(input))
+            }
+        }
+    }
+}
+
+/// The input `aoc fetch` saved for `day`, if there is one. Unlike the bundled input
+/// it's the user's own, so it's the one whose answers they can submit.
+pub fn fetched(day: &Day) -> io::Result<Option<String>> {
+    read_fetched(days::cache_directory(day.number))
+}
+
+fn read_fetched(cache: Option<PathBuf>) -> io::Result<Option<String>> {
+    let Some(dir) = cache else {
+        return Ok(None);
+    };
+    match fs::read_to_string(dir.join("input.txt")) {
+        Ok(input) if !input.is_empty() => Ok(Some(input)),
+        Ok(_) => Ok(None),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// The input fetched into `cache`, or else the bundled one.
+fn cached_or_bundled(day: &Day, cache: Option<PathBuf>) -> io::Result<Cow<'static, str>> {
+    Ok(match read_fetched(cache)? {
+        Some(input) => Cow::Owned(input),
+        None => Cow::Borrowed(day.input),
+    })
+}
+
+#[test]
+fn prefers_fetched_input_to_bundled() {
+    let day = days::find(1).unwrap();
+    let dir = crate::client::scratch_dir("input-precedence");
+    assert_eq!(
+        day.input,
+        cached_or_bundled(day, Some(dir.clone())).unwrap()
+    );
+    assert_eq!(day.input, cached_or_bundled(day, None).unwrap());
+
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("input.txt"), "").unwrap();
+    assert_eq!(
+        day.input,
+        cached_or_bundled(day, Some(dir.clone())).unwrap()
+    );
+    fs::write(dir.join("input.txt"), "199\n200\n").unwrap();
+    assert_eq!(
+        "199\n200\n",
+        cached_or_bundled(day, Some(dir.clone())).unwrap()
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
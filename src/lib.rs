@@ -0,0 +1,27 @@
+//! Solutions to Advent of Code 2021.
+//!
+//! Each puzzle lives in its own module under [`days`], and the `aoc` binary
+//! drives them all through the registry in that module.
+
+pub mod animate;
+pub mod answers;
+pub mod automaton;
+pub mod bench;
+pub mod client;
+pub mod colour;
+pub mod days;
+pub mod error;
+#[cfg(test)]
+mod fuzz;
+pub mod grid;
+pub mod http;
+pub mod image;
+pub mod input;
+pub mod ocr;
+pub mod parse;
+pub mod random;
+pub mod search;
+pub mod solution;
+pub mod submit;
+pub mod toml;
+mod work;
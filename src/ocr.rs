@@ -0,0 +1,126 @@
+//! Reading the capital letters some puzzles draw as their answer, in the 4x6
+//! font they all use. Letters are four dots wide with a blank column after each,
+//! apart from `Y`, which is five wide and fills the blank column too.
+
+use crate::grid::Grid;
+use rusttype::Point;
+
+const LETTERS: &str = "ABCEFGHIJKLOPRSUZY";
+
+/// Each of `LETTERS` in turn, drawn the way the puzzles draw them.
+const FONT: &str = "\
+.##..###...##..####.####..##..#..#..###...##.#..#.#.....##..###..###...###.#..#.####.#...#
+#..#.#..#.#..#.#....#....#..#.#..#...#.....#.#.#..#....#..#.#..#.#..#.#....#..#....#.#...#
+#..#.###..#....###..###..#....####...#.....#.##...#....#..#.#..#.#..#.#....#..#...#...#.#.
+####.#..#.#....#....#....#.##.#..#...#.....#.#.#..#....#..#.###..###...##..#..#..#.....#..
+#..#.#..#.#..#.#....#....#..#.#..#...#..#..#.#.#..#....#..#.#....#.#.....#.#..#.#......#..
+#..#.###...##..####.#.....###.#..#..###..##..#..#.####..##..#....#..#.###...##..####...#..";
+
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+
+/// Reads the letters drawn in `dots`, or `None` if it isn't a single row of
+/// letters from the font. The last letter's blank column may be left off.
+pub fn read(dots: &Grid<bool>) -> Option<String> {
+    if dots.height() != LETTER_HEIGHT || dots.width() < LETTER_WIDTH {
+        return None;
+    }
+    let count = (dots.width() + 1) / (LETTER_WIDTH + 1);
+    let trailing_blank = dots
+        .iter()
+        .filter(|(pos, _)| pos.x / (LETTER_WIDTH + 1) >= count)
+        .all(|(_, &dot)| !dot);
+    if !trailing_blank {
+        return None;
+    }
+
+    let font = font();
+    (0..count)
+        .map(|i| {
+            LETTERS
+                .chars()
+                .enumerate()
+                .find(|&(j, letter)| same_letter(dots, i, &font, j, width(letter)))
+                .map(|(_, letter)| letter)
+        })
+        .collect()
+}
+
+/// How many columns `letter` takes up before the blank one after it, if any.
+fn width(letter: char) -> usize {
+    match letter {
+        'Y' => LETTER_WIDTH + 1,
+        _ => LETTER_WIDTH,
+    }
+}
+
+fn font() -> Grid<bool> {
+    Grid::parse(FONT, "`#` or `.`", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+    .expect("the font is well-formed")
+}
+
+/// Whether the `i`th letter in `a` is the same as the `j`th in `b`, which is
+/// `width` wide. Any of the letter's blank column that `a` has must be blank.
+fn same_letter(a: &Grid<bool>, i: usize, b: &Grid<bool>, j: usize, width: usize) -> bool {
+    (0..LETTER_HEIGHT).all(|y| {
+        (0..=LETTER_WIDTH).all(|x| {
+            let at = |letter| Point {
+                x: letter * (LETTER_WIDTH + 1) + x,
+                y,
+            };
+            match x < width {
+                true => a.get(at(i)) == b.get(at(j)),
+                false => a.get(at(i)) != Some(&true),
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+fn dots(art: &str) -> Grid<bool> {
+    Grid::parse(art, "`#` or `.`", |c| Some(c == '#')).unwrap()
+}
+
+#[test]
+fn reads_every_letter() {
+    assert_eq!(Some(LETTERS.to_string()), read(&font()));
+    let with_gap = FONT
+        .lines()
+        .map(|line| format!("{}.\n", line))
+        .collect::<String>();
+    assert_eq!(Some(LETTERS.to_string()), read(&dots(&with_gap)));
+}
+
+#[test]
+fn reads_y_filling_its_blank_column() {
+    let art = "\
+#...##..#.
+#...##..#.
+.#.#.####.
+..#..#..#.
+..#..#..#.
+..#..#..#.";
+    assert_eq!(Some("YH".to_string()), read(&dots(art)));
+}
+
+#[test]
+fn rejects_anything_else() {
+    // A mangled H.
+    assert_eq!(None, read(&dots("#..#\n#..#\n####\n#..#\n#.##\n#..#")));
+    // An H with a dot in the gap after it.
+    assert_eq!(
+        None,
+        read(&dots("#..##\n#..#.\n####.\n#..#.\n#..#.\n#..#."))
+    );
+    // A Y missing the end of its right arm.
+    assert_eq!(
+        None,
+        read(&dots("#....\n#...#\n.#.#.\n..#..\n..#..\n..#.."))
+    );
+    // Too short.
+    assert_eq!(None, read(&dots("#..#\n#..#\n####\n#..#\n#..#")));
+}
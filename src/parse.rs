@@ -0,0 +1,330 @@
+//! Small parser combinators for the puzzles' line-based formats. A parser reads
+//! from the start of a piece of a line and hands back what it read along with
+//! the rest of the line. When it fails it points at the slice of the line it got
+//! stuck on, which [`line`] turns into a [`ParseError`] once it's known which
+//! line that was.
+
+use crate::error::{parse_lines, ParseError};
+use std::str::FromStr;
+
+/// Where in a line a parser got stuck, and what it expected to find there.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Failure<'a> {
+    /// A slice of the line, so the error can say which column it starts at.
+    pub at: &'a str,
+    pub message: String,
+}
+
+impl<'a> Failure<'a> {
+    pub fn new(at: &'a str, message: impl Into<String>) -> Self {
+        Failure {
+            at,
+            message: message.into(),
+        }
+    }
+}
+
+/// What a parser read, and the rest of the line after it.
+pub type Parsed<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// Anything that reads a `T` from the start of a string. Every combinator here
+/// returns one, and any closure of the right shape is one too.
+pub trait Parser<'a, T>: Fn(&'a str) -> Parsed<'a, T> {}
+
+impl<'a, T, F: Fn(&'a str) -> Parsed<'a, T>> Parser<'a, T> for F {}
+
+/// Exactly the text `expected`.
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, ()> {
+    move |s: &'a str| match s.strip_prefix(expected) {
+        Some(rest) => Ok(((), rest)),
+        None => Err(Failure::new(s, format!("expected `{}`", expected))),
+    }
+}
+
+/// An integer, possibly negative, described as `what` if there isn't one.
+pub fn integer<'a, T: FromStr>(what: &'static str) -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        // Take the whole alphanumeric run, so that `12x` is reported as not
+        // being a number rather than as a 12 followed by something unexpected.
+        let sign = s.starts_with('-') as usize;
+        let end = s[sign..]
+            .find(|c: char| !c.is_alphanumeric())
+            .map_or(s.len(), |i| sign + i);
+        let token = &s[..end];
+        match token.parse() {
+            Ok(n) => Ok((n, &s[end..])),
+            Err(_) if token.is_empty() => Err(Failure::new(token, format!("expected {}", what))),
+            Err(_) => Err(Failure::new(
+                token,
+                format!("expected {}, found `{}`", what, token),
+            )),
+        }
+    }
+}
+
+/// A run of letters and digits, described as `what` if there isn't one.
+pub fn word<'a>(what: &'static str) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| {
+        let end = s.find(|c: char| !c.is_alphanumeric()).unwrap_or(s.len());
+        if end == 0 {
+            return Err(Failure::new(&s[..0], format!("expected {}", what)));
+        }
+        Ok((&s[..end], &s[end..]))
+    }
+}
+
+/// Whichever of the `options`' names comes next, giving its value.
+pub fn choice<'a, T: Clone>(options: &'static [(&'static str, T)]) -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        options
+            .iter()
+            .find_map(|(name, value)| s.strip_prefix(name).map(|rest| (value.clone(), rest)))
+            .ok_or_else(|| {
+                let names = options
+                    .iter()
+                    .map(|(name, _)| format!("`{}`", name))
+                    .collect::<Vec<_>>();
+                let listed = match names.split_last() {
+                    Some((last, [])) => last.clone(),
+                    Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+                    None => "nothing".to_string(),
+                };
+                Failure::new(s, format!("expected {}", listed))
+            })
+    }
+}
+
+/// `first` and `second` either side of `separator`. `first` only gets to see up
+/// to where the separator first appears, which is what lets it be a list.
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    separator: &'static str,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |s: &'a str| {
+        let expected = || format!("expected `{}`", separator);
+        let at = s
+            .find(separator)
+            .ok_or_else(|| Failure::new(s, expected()))?;
+        let (a, rest) = first(&s[..at])?;
+        if !rest.is_empty() {
+            return Err(Failure::new(rest, expected()));
+        }
+        let (b, rest) = second(&s[at + separator.len()..])?;
+        Ok(((a, b), rest))
+    }
+}
+
+/// One or more `item`s with `separator` between each.
+pub fn separated<'a, T>(
+    item: impl Parser<'a, T>,
+    separator: &'static str,
+) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let (first, mut rest) = item(s)?;
+        let mut items = vec![first];
+        while let Some(after) = rest.strip_prefix(separator) {
+            let (next, after) = item(after)?;
+            items.push(next);
+            rest = after;
+        }
+        Ok((items, rest))
+    }
+}
+
+/// Every space-separated `item` up to the end, however many spaces there are
+/// between them. There may be none at all.
+pub fn words<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let mut items = vec![];
+        let mut rest = s.trim_start_matches(' ');
+        while !rest.is_empty() {
+            let (next, after) = item(rest)?;
+            if !after.is_empty() && !after.starts_with(' ') {
+                return Err(Failure::new(after, "expected a space"));
+            }
+            items.push(next);
+            rest = after.trim_start_matches(' ');
+        }
+        Ok((items, rest))
+    }
+}
+
+pub fn preceded<'a, T>(prefix: &'static str, parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    let prefix = literal(prefix);
+    move |s: &'a str| {
+        let ((), rest) = prefix(s)?;
+        parser(rest)
+    }
+}
+
+pub fn terminated<'a, T>(parser: impl Parser<'a, T>, suffix: &'static str) -> impl Parser<'a, T> {
+    let suffix = literal(suffix);
+    move |s: &'a str| {
+        let (value, rest) = parser(s)?;
+        let ((), rest) = suffix(rest)?;
+        Ok((value, rest))
+    }
+}
+
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |s: &'a str| parser(s).map(|(value, rest)| (f(value), rest))
+}
+
+/// Rejects what `parser` read if `f` finds something wrong with it, pointing at
+/// everything it read.
+pub fn check<'a, T>(
+    parser: impl Parser<'a, T>,
+    f: impl Fn(&T) -> Result<(), String>,
+) -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        let (value, rest) = parser(s)?;
+        f(&value).map_err(|message| Failure::new(&s[..s.len() - rest.len()], message))?;
+        Ok((value, rest))
+    }
+}
+
+/// Parses the whole of `text`, which is line `lineno` of the input.
+pub fn line<'a, T>(
+    lineno: usize,
+    text: &'a str,
+    parser: impl Parser<'a, T>,
+) -> Result<T, ParseError> {
+    let failure = match parser(text) {
+        Ok((value, "")) => return Ok(value),
+        Ok((_, rest)) => Failure::new(rest, "expected the end of the line"),
+        Err(failure) => failure,
+    };
+    Err(ParseError::new(lineno, text, failure.at, failure.message))
+}
+
+/// Parses every line of `input` the same way.
+pub fn lines<'a, T>(input: &'a str, parser: impl Parser<'a, T>) -> Result<Vec<T>, ParseError> {
+    parse_lines(input, |lineno, text| line(lineno, text, &parser))
+}
+
+/// A run of lines with blank lines either side, or the start or end of the input.
+pub struct Block<'a> {
+    /// Each line with its 1-based line number in the whole input. Never empty.
+    lines: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Block<'a> {
+    pub fn lines(&self) -> &[(usize, &'a str)] {
+        &self.lines
+    }
+
+    /// Parses every line of the block the same way.
+    pub fn parse_lines<T>(&self, parser: impl Parser<'a, T>) -> Result<Vec<T>, ParseError> {
+        self.lines
+            .iter()
+            .map(|&(lineno, text)| line(lineno, text, &parser))
+            .collect()
+    }
+
+    /// Parses a block that should be a single line.
+    pub fn parse_line<T>(&self, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+        if let Some(&(lineno, text)) = self.lines.get(1) {
+            return Err(ParseError::new(lineno, text, text, "expected a blank line"));
+        }
+        let (lineno, text) = self.lines[0];
+        line(lineno, text, parser)
+    }
+
+    /// An error for a block that stopped before it should have.
+    pub fn ended_early(&self, message: impl Into<String>) -> ParseError {
+        let &(lineno, text) = self.lines.last().unwrap();
+        ParseError::new(lineno, text, &text[text.len()..], message)
+    }
+}
+
+/// Splits `input` up at its blank lines. Several blank lines in a row count as one.
+pub fn blocks(input: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut lines = vec![];
+    for (i, text) in input.lines().enumerate() {
+        if text.is_empty() {
+            if !lines.is_empty() {
+                blocks.push(Block {
+                    lines: std::mem::take(&mut lines),
+                });
+            }
+        } else {
+            lines.push((i + 1, text));
+        }
+    }
+    if !lines.is_empty() {
+        blocks.push(Block { lines });
+    }
+    blocks
+}
+
+#[test]
+fn combinators() {
+    let range = pair(integer::<i32>("a number"), "..", integer("a number"));
+    let ranges = preceded("x=", separated(range, ","));
+    assert_eq!(Ok(vec![(-3, 5), (7, 7)]), line(1, "x=-3..5,7..7", &ranges));
+
+    for (text, column, message) in [
+        ("y=1..2", 1, "expected `x=`"),
+        ("x=1..2,3", 8, "expected `..`"),
+        ("x=1..2x", 6, "expected a number, found `2x`"),
+        ("x=1..2 ", 7, "expected the end of the line"),
+        ("x=..2", 3, "expected a number"),
+    ] {
+        let error = line(3, text, &ranges).unwrap_err();
+        assert_eq!((3, column), (error.line, error.column), "{:?}", text);
+        assert_eq!(message, error.message, "{:?}", text);
+    }
+
+    let move_by = pair(
+        choice(&[("up", -1), ("down", 1)]),
+        " ",
+        integer::<i32>("a distance"),
+    );
+    let move_by = map(move_by, |(direction, distance)| direction * distance);
+    assert_eq!(Ok(-2), line(1, "up 2", &move_by));
+    let error = line(1, "left 2", &move_by).unwrap_err();
+    assert_eq!(
+        (1, "expected `up` or `down`"),
+        (error.column, error.message.as_str())
+    );
+
+    let small = check(integer::<u8>("a digit"), |&n| match n {
+        0..=9 => Ok(()),
+        _ => Err("too big".to_string()),
+    });
+    assert_eq!(Ok(vec![1, 2]), line(1, " 1  2 ", words(&small)));
+    let error = line(1, "1 23", words(&small)).unwrap_err();
+    assert_eq!((3, "too big"), (error.column, error.message.as_str()));
+}
+
+#[test]
+fn blocks_of_lines() {
+    let input = "1,2\n\n\n3\n4\n\nfive\n";
+    let blocks = blocks(input);
+    assert_eq!(3, blocks.len());
+    assert_eq!(
+        Ok(vec![1, 2]),
+        blocks[0].parse_line(separated(integer::<u8>("a number"), ","))
+    );
+    assert_eq!(
+        Ok(vec![3, 4]),
+        blocks[1].parse_lines(integer::<u8>("a number"))
+    );
+    assert_eq!(
+        5,
+        blocks[1]
+            .parse_line(integer::<u8>("a number"))
+            .unwrap_err()
+            .line
+    );
+    let error = blocks[2]
+        .parse_lines(integer::<u8>("a number"))
+        .unwrap_err();
+    assert_eq!(
+        (7, "expected a number, found `five`"),
+        (error.line, error.message.as_str())
+    );
+    assert_eq!(5, blocks[2].ended_early("expected more").column);
+}
@@ -0,0 +1,80 @@
+//! A small seeded random number generator, for making up puzzle inputs. The same
+//! seed always gives the same numbers, on any machine.
+
+use crate::answers::Answers;
+use std::ops::Range;
+
+/// A day's input generator, making up an input in the day's format. What `scale`
+/// counts depends on the day, but bigger always means a bigger input.
+pub type Generator = fn(rng: &mut Rng, scale: usize) -> String;
+
+/// A generator that also knows some of the answers to the input it makes up,
+/// having built them in. Makes the same input from the same seed as the day's
+/// [`Generator`].
+pub type AnsweredGenerator = fn(rng: &mut Rng, scale: usize) -> (String, Answers);
+
+/// SplitMix64: fast, tiny, and plenty random enough for test inputs, though
+/// nothing else.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which mustn't be empty.
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(!range.is_empty(), "empty range {:?}", range);
+        let span = range.end.abs_diff(range.start);
+        // The bias from taking the remainder is negligible for spans this far
+        // below 2^64.
+        range.start.wrapping_add((self.next_u64() % span) as i64)
+    }
+
+    /// A number from 0 up to but not including `n`, which mustn't be 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        self.range(0..n as i64) as usize
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[test]
+fn seeded_and_in_range() {
+    let first = (0..5).map(|_| Rng::new(7).next_u64()).collect::<Vec<_>>();
+    assert!(first.iter().all(|&n| n == first[0]));
+
+    let mut rng = Rng::new(7);
+    let rolls = (0..1000).map(|_| rng.range(-3..4)).collect::<Vec<_>>();
+    assert!(rolls.iter().all(|roll| (-3..4).contains(roll)));
+    assert!((-3..4).all(|n| rolls.contains(&n)));
+
+    let mut items = [1, 2, 3, 4, 5];
+    rng.shuffle(&mut items);
+    items.sort();
+    assert_eq!([1, 2, 3, 4, 5], items);
+}
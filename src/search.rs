@@ -0,0 +1,155 @@
+//! Shortest-path search over any graph given as a neighbour function.
+
+use crate::work;
+use rusttype::Point;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// The cheapest way found to a goal: its total cost, and every node along it
+/// from the start to the goal inclusive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Path<N> {
+    pub cost: usize,
+    pub nodes: Vec<N>,
+}
+
+/// Dijkstra's algorithm: the cheapest path from `start` to any node satisfying
+/// `is_goal`, where `neighbours` gives each node's neighbours along with the
+/// cost of the edge to them.
+pub fn dijkstra<N, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+/// A*: like [`dijkstra`], but guided by a `heuristic` estimate of the cost still
+/// to go. The estimate must never be more than the real cost, or the path found
+/// may not be the cheapest.
+pub fn astar<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> usize,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    // Nodes are numbered in the order they're found, so that the queue and the
+    // parent links only have to deal in indices.
+    let mut nodes = vec![start.clone()];
+    let mut indices = HashMap::from([(start, 0)]);
+    let mut best_costs = vec![0];
+    let mut parents: Vec<Option<usize>> = vec![None];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&nodes[0]), 0, 0))]);
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        work::step();
+        if cost > best_costs[index] {
+            // Already reached more cheaply.
+            continue;
+        }
+        if is_goal(&nodes[index]) {
+            return Some(Path {
+                cost,
+                nodes: reconstruct(&nodes, &parents, index),
+            });
+        }
+        for (neighbour, edge_cost) in neighbours(&nodes[index]) {
+            let new_cost = cost + edge_cost;
+            let neighbour_index = match indices.entry(neighbour) {
+                Entry::Occupied(entry) => {
+                    let neighbour_index = *entry.get();
+                    if new_cost >= best_costs[neighbour_index] {
+                        continue;
+                    }
+                    best_costs[neighbour_index] = new_cost;
+                    parents[neighbour_index] = Some(index);
+                    neighbour_index
+                }
+                Entry::Vacant(entry) => {
+                    nodes.push(entry.key().clone());
+                    best_costs.push(new_cost);
+                    parents.push(Some(index));
+                    *entry.insert(nodes.len() - 1)
+                }
+            };
+            let estimate = new_cost + heuristic(&nodes[neighbour_index]);
+            queue.push(Reverse((estimate, new_cost, neighbour_index)));
+        }
+    }
+
+    None
+}
+
+fn reconstruct<N: Clone>(nodes: &[N], parents: &[Option<usize>], goal: usize) -> Vec<N> {
+    let mut path = vec![];
+    let mut current = Some(goal);
+    while let Some(index) = current {
+        path.push(nodes[index].clone());
+        current = parents[index];
+    }
+    path.reverse();
+    path
+}
+
+/// The distance between two grid positions moving only along rows and columns,
+/// which never overestimates when every step costs at least one.
+pub fn manhattan(a: Point<usize>, b: Point<usize>) -> usize {
+    a.x.abs_diff(b.x) + a.y.abs_diff(b.y)
+}
+
+#[test]
+fn finds_the_cheapest_path() {
+    // a -1- b -1- c, with a direct but expensive a -5- c edge.
+    let edges = |node: &char| match node {
+        'a' => vec![('b', 1), ('c', 5)],
+        'b' => vec![('a', 1), ('c', 1)],
+        'c' => vec![('a', 5), ('b', 1)],
+        _ => vec![],
+    };
+
+    let path = dijkstra('a', edges, |&node| node == 'c').unwrap();
+    assert_eq!(2, path.cost);
+    assert_eq!(vec!['a', 'b', 'c'], path.nodes);
+    assert_eq!(None, dijkstra('a', edges, |&node| node == 'z'));
+}
+
+#[test]
+fn astar_agrees_with_dijkstra() {
+    let size = 20;
+    let goal = Point {
+        x: size - 1,
+        y: size - 1,
+    };
+    // A cost that varies enough to make the path wander.
+    let cost = |p: Point<usize>| (p.x * 7 + p.y * 13) % 5 + 1;
+    let neighbours = |&p: &Point<usize>| {
+        [(1, 0), (0, 1), (-1, 0), (0, -1)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                Some(Point {
+                    x: p.x.checked_add_signed(dx).filter(|&x| x < size)?,
+                    y: p.y.checked_add_signed(dy).filter(|&y| y < size)?,
+                })
+            })
+            .map(move |n| (n, cost(n)))
+    };
+    let start = Point { x: 0, y: 0 };
+
+    let by_dijkstra = dijkstra(start, neighbours, |&p| p == goal).unwrap();
+    let by_astar = astar(start, neighbours, |&p| manhattan(p, goal), |&p| p == goal).unwrap();
+    assert_eq!(by_dijkstra.cost, by_astar.cost);
+    assert_eq!(
+        by_astar.cost,
+        by_astar.nodes[1..].iter().map(|&p| cost(p)).sum::<usize>()
+    );
+}
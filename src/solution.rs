@@ -0,0 +1,178 @@
+//! The shape every day's solution shares.
+
+use crate::days::{Day, Part};
+use crate::error::ParseError;
+use std::any::Any;
+use std::fmt::{self, Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A day's puzzle: a parse step producing `Parsed`, and the two parts which
+/// each compute an answer from it.
+pub trait Solution {
+    type Parsed: 'static;
+    type Answer1: PartAnswer;
+    type Answer2: PartAnswer;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(parsed: &Self::Parsed) -> Self::Answer1;
+    fn part2(parsed: &Self::Parsed) -> Self::Answer2;
+}
+
+/// The answer to a part that an input can parse and still not have, such as
+/// when the amphipods are deadlocked. Shows why in place of the answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer<T> {
+    Found(T),
+    Impossible(String),
+}
+
+impl<T> Answer<T> {
+    pub fn found_or(answer: Option<T>, why: impl Into<String>) -> Self {
+        match answer {
+            Some(answer) => Answer::Found(answer),
+            None => Answer::Impossible(why.into()),
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Answer<U> {
+        match self {
+            Answer::Found(answer) => Answer::Found(f(answer)),
+            Answer::Impossible(why) => Answer::Impossible(why),
+        }
+    }
+}
+
+impl<T: Display> Display for Answer<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Answer::Found(answer) => answer.fmt(f),
+            Answer::Impossible(why) => write!(f, "no answer: {}", why),
+        }
+    }
+}
+
+/// The type of a part's answer, which can be shown as text once it's erased.
+pub trait PartAnswer: Display {
+    fn erase(&self) -> Answer<String> {
+        Answer::Found(self.to_string())
+    }
+}
+
+impl PartAnswer for i64 {}
+impl PartAnswer for isize {}
+impl PartAnswer for u32 {}
+impl PartAnswer for u64 {}
+impl PartAnswer for usize {}
+impl PartAnswer for String {}
+
+impl<T: Display> PartAnswer for Answer<T> {
+    fn erase(&self) -> Answer<String> {
+        match self {
+            Answer::Found(answer) => Answer::Found(answer.to_string()),
+            Answer::Impossible(why) => Answer::Impossible(why.clone()),
+        }
+    }
+}
+
+/// A [`Solution`] with its types erased, so that every day can sit in the same
+/// registry. Blanket-implemented for all solutions.
+pub trait AnySolution: Sync {
+    fn parse_any(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+
+    /// Panics if `parsed` didn't come from this solution's `parse_any`.
+    fn solve_any(&self, parsed: &dyn Any, part: Part) -> Answer<String>;
+}
+
+impl<S: Solution + Sync> AnySolution for S {
+    fn parse_any(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        S::parse(input).map(|parsed| Box::new(parsed) as Box<dyn Any>)
+    }
+
+    fn solve_any(&self, parsed: &dyn Any, part: Part) -> Answer<String> {
+        let parsed = parsed
+            .downcast_ref::<S::Parsed>()
+            .expect("parsed input came from a different solution");
+        match part {
+            Part::One => S::part1(parsed).erase(),
+            Part::Two => S::part2(parsed).erase(),
+        }
+    }
+}
+
+/// A value along with how long it took to compute.
+pub struct Timed<T> {
+    pub value: T,
+    pub elapsed: Duration,
+}
+
+pub fn timed<T>(f: impl FnOnce() -> T) -> Timed<T> {
+    let start = Instant::now();
+    let value = f();
+    Timed {
+        value,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// The answers from one run of a day, with the parse and each part timed separately.
+pub struct Run {
+    pub parse_time: Duration,
+    pub answers: Vec<(Part, Timed<Answer<String>>)>,
+}
+
+pub fn run(day: &Day, input: &str, parts: &[Part]) -> Result<Run, ParseError> {
+    let parsed = timed(|| day.solution.parse_any(input));
+    let parsed_value = parsed.value.map_err(|e| e.in_day(day.number))?;
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            (
+                part,
+                timed(|| day.solution.solve_any(parsed_value.as_ref(), part)),
+            )
+        })
+        .collect();
+    Ok(Run {
+        parse_time: parsed.elapsed,
+        answers,
+    })
+}
+
+/// Runs each day on its input across up to `jobs` threads, returning the results
+/// in the same order as `inputs`.
+pub fn run_parallel(
+    inputs: &[(&Day, &str)],
+    parts: &[Part],
+    jobs: usize,
+) -> Vec<Result<Run, ParseError>> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(inputs.len()) {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&(day, input)) = inputs.get(index) else {
+                    break;
+                };
+                sender.send((index, run(day, input, parts))).unwrap();
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results = receiver.into_iter().collect::<Vec<_>>();
+    results.sort_by_key(|&(index, _)| index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Parses `input` and solves both parts, for checking a day against its worked example.
+#[cfg(test)]
+pub(crate) fn solve<S: Solution>(input: &str) -> (S::Answer1, S::Answer2) {
+    let parsed = S::parse(input).expect("example input should parse");
+    (S::part1(&parsed), S::part2(&parsed))
+}
@@ -0,0 +1,431 @@
+//! Submitting answers, and the history of attempts that stops us resubmitting
+//! one the site has already turned down (or hammering it while it wants us to wait).
+
+use crate::client::Client;
+use crate::days::Part;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What the site made of a submitted answer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Right,
+    Wrong(Option<Hint>),
+    /// Submitted too soon after the last attempt; try again in this many seconds.
+    Wait(u64),
+    /// The part had already been solved, so the answer wasn't checked.
+    AlreadySolved,
+    /// A response we couldn't make sense of.
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Outcome {
+    /// Reads the outcome out of the page the site responds with.
+    pub fn from_response(page: &str) -> Self {
+        if page.contains("That's the right answer") {
+            Outcome::Right
+        } else if page.contains("That's not the right answer") {
+            Outcome::Wrong(if page.contains("your answer is too high") {
+                Some(Hint::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            })
+        } else if page.contains("You gave an answer too recently") {
+            Outcome::Wait(wait_seconds(page).unwrap_or(60))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    /// How long the site makes us wait before the next answer. A wrong answer costs at least
+    /// a minute, or more after several; if we try too soon the site says so, and that wait is
+    /// recorded as well.
+    fn cooldown(&self) -> u64 {
+        match self {
+            Outcome::Wrong(_) => WRONG_ANSWER_WAIT,
+            Outcome::Wait(seconds) => *seconds,
+            _ => 0,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Outcome::Right => "right".to_string(),
+            Outcome::Wrong(None) => "wrong".to_string(),
+            Outcome::Wrong(Some(Hint::TooHigh)) => "too-high".to_string(),
+            Outcome::Wrong(Some(Hint::TooLow)) => "too-low".to_string(),
+            Outcome::Wait(seconds) => format!("wait-{}", seconds),
+            Outcome::AlreadySolved => "already-solved".to_string(),
+            Outcome::Unknown => "unknown".to_string(),
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        Some(match label {
+            "right" => Outcome::Right,
+            "wrong" => Outcome::Wrong(None),
+            "too-high" => Outcome::Wrong(Some(Hint::TooHigh)),
+            "too-low" => Outcome::Wrong(Some(Hint::TooLow)),
+            "already-solved" => Outcome::AlreadySolved,
+            "unknown" => Outcome::Unknown,
+            _ => Outcome::Wait(label.strip_prefix("wait-")?.parse().ok()?),
+        })
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Right => f.write_str("that's the right answer"),
+            Outcome::Wrong(None) => f.write_str("that's not the right answer"),
+            Outcome::Wrong(Some(Hint::TooHigh)) => f.write_str("too high"),
+            Outcome::Wrong(Some(Hint::TooLow)) => f.write_str("too low"),
+            Outcome::Wait(seconds) => write!(f, "answered too recently; wait {}s", seconds),
+            Outcome::AlreadySolved => f.write_str("that part is already solved"),
+            Outcome::Unknown => f.write_str("couldn't understand the response"),
+        }
+    }
+}
+
+const WRONG_ANSWER_WAIT: u64 = 60;
+
+/// Picks the wait out of "You have 1m 30s left to wait."
+fn wait_seconds(page: &str) -> Option<u64> {
+    let words = page.split_whitespace().collect::<Vec<_>>();
+    if let Some(left) = words.windows(3).position(|w| w == ["left", "to", "wait."]) {
+        let seconds = words[..left]
+            .iter()
+            .rev()
+            .map_while(|word| {
+                let (number, unit) = word.split_at(word.find(|c: char| !c.is_ascii_digit())?);
+                let number: u64 = number.parse().ok()?;
+                match unit {
+                    "s" => Some(number),
+                    "m" => Some(number * 60),
+                    "h" => Some(number * 3600),
+                    _ => None,
+                }
+            })
+            .sum();
+        return Some(seconds);
+    }
+    None
+}
+
+/// One answer we've sent, and what came of it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attempt {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub part: Part,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Why an answer wasn't sent.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Refusal {
+    AlreadyRight(String),
+    /// The site said the part was already solved, by an answer we don't know.
+    AlreadySolved,
+    KnownWrong(Outcome),
+    /// Another attempt was already turned down as too high (or low) for this one to be right.
+    OutOfBounds(Hint, String),
+    TooSoon(u64),
+    MultiLine,
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyRight(answer) => write!(f, "already solved, with {}", answer),
+            Refusal::AlreadySolved => f.write_str("already solved"),
+            Refusal::KnownWrong(outcome) => {
+                write!(f, "already submitted and rejected: {}", outcome)
+            }
+            Refusal::OutOfBounds(Hint::TooHigh, bound) => {
+                write!(f, "can't be right, since {} was already too high", bound)
+            }
+            Refusal::OutOfBounds(Hint::TooLow, bound) => {
+                write!(f, "can't be right, since {} was already too low", bound)
+            }
+            Refusal::TooSoon(seconds) => {
+                write!(f, "the site asked us to wait; try again in {}s", seconds)
+            }
+            Refusal::MultiLine => f.write_str("the answer has to be read off by eye"),
+        }
+    }
+}
+
+/// Every attempt at a day, kept in a tab-separated file of
+/// `time  part  outcome  answer` lines.
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Loads the history at `path`, which is empty if the file doesn't exist yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        let attempts = text
+            .lines()
+            .map(|line| {
+                parse_attempt(line).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unexpected history line `{}`", line),
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(History {
+            path: path.to_path_buf(),
+            attempts,
+        })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Whether `answer` is worth sending at `now`, as far as we already know.
+    pub fn check(&self, part: Part, answer: &str, now: u64) -> Result<(), Refusal> {
+        if answer.contains('\n') {
+            return Err(Refusal::MultiLine);
+        }
+        let attempts = self.attempts.iter().filter(|a| a.part == part);
+        for attempt in attempts.clone() {
+            match attempt.outcome {
+                Outcome::Right => return Err(Refusal::AlreadyRight(attempt.answer.clone())),
+                Outcome::AlreadySolved => return Err(Refusal::AlreadySolved),
+                Outcome::Wrong(_) if attempt.answer == answer => {
+                    return Err(Refusal::KnownWrong(attempt.outcome))
+                }
+                _ => {}
+            }
+        }
+        if let Ok(value) = answer.parse::<i128>() {
+            for attempt in attempts {
+                let Ok(bound) = attempt.answer.parse::<i128>() else {
+                    continue;
+                };
+                match attempt.outcome {
+                    Outcome::Wrong(Some(Hint::TooHigh)) if value >= bound => {
+                        return Err(Refusal::OutOfBounds(Hint::TooHigh, attempt.answer.clone()))
+                    }
+                    Outcome::Wrong(Some(Hint::TooLow)) if value <= bound => {
+                        return Err(Refusal::OutOfBounds(Hint::TooLow, attempt.answer.clone()))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        // The wait applies to every part of every day, but only ours are on record.
+        if let Some(last) = self.attempts.last() {
+            let ready = last.time + last.outcome.cooldown();
+            if now < ready {
+                return Err(Refusal::TooSoon(ready - now));
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds an attempt, saving it straight away.
+    pub fn record(&mut self, attempt: Attempt) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            attempt.time,
+            attempt.part,
+            attempt.outcome.label(),
+            attempt.answer
+        )?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+fn parse_attempt(line: &str) -> Option<Attempt> {
+    let mut fields = line.splitn(4, '\t');
+    let time = fields.next()?.parse().ok()?;
+    let part = match fields.next()? {
+        "part1" => Part::One,
+        "part2" => Part::Two,
+        _ => return None,
+    };
+    let outcome = Outcome::from_label(fields.next()?)?;
+    let answer = fields.next()?.to_string();
+    Some(Attempt {
+        time,
+        part,
+        answer,
+        outcome,
+    })
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Sends `answer` unless the history says there's no point, and records what
+/// the site made of it.
+pub fn submit(
+    client: &Client,
+    history: &mut History,
+    day: u8,
+    part: Part,
+    answer: &str,
+    now: u64,
+) -> io::Result<Result<Outcome, Refusal>> {
+    if let Err(refusal) = history.check(part, answer, now) {
+        return Ok(Err(refusal));
+    }
+    let outcome = client.submit_answer(day, part, answer)?;
+    history.record(Attempt {
+        time: now,
+        part,
+        answer: answer.to_string(),
+        outcome,
+    })?;
+    Ok(Ok(outcome))
+}
+
+#[cfg(test)]
+const WRONG_TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.</p></article>";
+
+#[cfg(test)]
+const RIGHT: &str =
+    "<article><p>That's the right answer!  You are one gold star closer to saving your vacation.</p></article>";
+
+#[test]
+fn reads_responses() {
+    assert_eq!(Outcome::Right, Outcome::from_response(RIGHT));
+    assert_eq!(
+        Outcome::Wrong(Some(Hint::TooHigh)),
+        Outcome::from_response(WRONG_TOO_HIGH)
+    );
+    assert_eq!(
+        Outcome::Wrong(Some(Hint::TooLow)),
+        Outcome::from_response("That's not the right answer; your answer is too low.")
+    );
+    assert_eq!(
+        Outcome::Wait(95),
+        Outcome::from_response(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 35s left to wait."
+        )
+    );
+    assert_eq!(
+        Outcome::AlreadySolved,
+        Outcome::from_response(
+            "You don't seem to be solving the right level.  Did you already complete it?"
+        )
+    );
+}
+
+#[test]
+fn never_resubmits_a_known_wrong_answer() {
+    use crate::client::{scratch_dir, Config};
+    use crate::http::stand_in_server;
+
+    let (base_url, server) = stand_in_server(vec![(200, WRONG_TOO_HIGH), (200, RIGHT)]);
+    let client = Client::new(Config {
+        session: "53cr37".to_string(),
+        base_url,
+    });
+    let dir = scratch_dir("submit");
+    let path = dir.join("submissions.tsv");
+    let mut history = History::load(&path).unwrap();
+
+    let too_high = Outcome::Wrong(Some(Hint::TooHigh));
+    assert_eq!(
+        Ok(too_high),
+        submit(&client, &mut history, 3, Part::One, "500", 1000).unwrap()
+    );
+    // The stand-in only has two responses, so any of these reaching it would
+    // use up the one meant for the right answer.
+    let mut history = History::load(&path).unwrap();
+    assert_eq!(
+        Err(Refusal::KnownWrong(too_high)),
+        submit(&client, &mut history, 3, Part::One, "500", 2000).unwrap()
+    );
+    assert_eq!(
+        Err(Refusal::OutOfBounds(Hint::TooHigh, "500".to_string())),
+        submit(&client, &mut history, 3, Part::One, "600", 2000).unwrap()
+    );
+    assert_eq!(
+        Ok(Outcome::Right),
+        submit(&client, &mut history, 3, Part::One, "400", 2000).unwrap()
+    );
+    assert_eq!(
+        Err(Refusal::AlreadyRight("400".to_string())),
+        submit(&client, &mut history, 3, Part::One, "401", 3000).unwrap()
+    );
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("POST /2021/day/3/answer HTTP/1.0\r\n"));
+    assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=500"));
+    assert_eq!(2, History::load(&path).unwrap().attempts().len());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn waits_when_asked_to() {
+    let dir = crate::client::scratch_dir("submit-wait");
+    let mut history = History::load(&dir.join("submissions.tsv")).unwrap();
+    history
+        .record(Attempt {
+            time: 1000,
+            part: Part::Two,
+            answer: "7".to_string(),
+            outcome: Outcome::Wait(30),
+        })
+        .unwrap();
+
+    assert_eq!(
+        Err(Refusal::TooSoon(10)),
+        history.check(Part::Two, "7", 1020)
+    );
+    assert_eq!(Ok(()), history.check(Part::Two, "7", 1030));
+
+    history
+        .record(Attempt {
+            time: 1030,
+            part: Part::Two,
+            answer: "7".to_string(),
+            outcome: Outcome::Wrong(None),
+        })
+        .unwrap();
+    assert_eq!(
+        Err(Refusal::TooSoon(45)),
+        history.check(Part::One, "8", 1045)
+    );
+    assert_eq!(Ok(()), history.check(Part::One, "8", 1090));
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,143 @@
+//! Reading the sliver of TOML our small files need: `key = value` lines whose
+//! value is an integer, a `"basic string"` or a `'''multi-line literal'''`, plus
+//! comments and blank lines. Tables, arrays and the rest aren't supported.
+
+use crate::error::ParseError;
+
+/// One `key = value` line, or lines for a multi-line value.
+pub struct Entry<'a> {
+    pub line: usize,
+    pub line_text: &'a str,
+    pub key: &'a str,
+    /// The value with its quotes removed and escapes resolved. Integers are kept as written.
+    pub value: String,
+}
+
+impl Entry<'_> {
+    /// An error pointing at this entry's key, for keys the file shouldn't have.
+    pub fn unexpected_key(&self, expected: &str) -> ParseError {
+        ParseError::new(
+            self.line,
+            self.line_text,
+            self.key,
+            format!("expected {}", expected),
+        )
+    }
+}
+
+/// Reads every entry in `text`, rejecting any key given twice.
+pub fn entries(text: &str) -> Result<Vec<Entry<'_>>, ParseError> {
+    let mut entries: Vec<Entry> = vec![];
+    let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+    while let Some((lineno, line)) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let (key, value) = trimmed
+            .split_once('=')
+            .ok_or_else(|| ParseError::new(lineno, line, trimmed, "expected `key = value`"))?;
+        let key = key.trim();
+        let value = value.trim();
+        if entries.iter().any(|entry| entry.key == key) {
+            return Err(ParseError::new(lineno, line, key, "key given twice"));
+        }
+
+        let value = if let Some(rest) = value.strip_prefix("'''") {
+            read_multi_line(text, lineno, line, rest, &mut lines)?
+        } else if let Some(rest) = value.strip_prefix('"') {
+            read_basic(lineno, line, rest)?
+        } else if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == '-') {
+            value.to_string()
+        } else {
+            return Err(ParseError::new(
+                lineno,
+                line,
+                value,
+                "expected an integer or a string",
+            ));
+        };
+        entries.push(Entry {
+            line: lineno,
+            line_text: line,
+            key,
+            value,
+        });
+    }
+    Ok(entries)
+}
+
+/// Reads a `"basic string"`, given what follows its opening quote.
+fn read_basic(lineno: usize, line: &str, rest: &str) -> Result<String, ParseError> {
+    let mut value = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' if rest[i + 1..].trim().is_empty() => return Ok(value),
+            '"' => {
+                return Err(ParseError::new(
+                    lineno,
+                    line,
+                    &rest[i + 1..],
+                    "unexpected characters after the string",
+                ))
+            }
+            '\\' => match chars.next() {
+                Some((_, '"')) => value.push('"'),
+                Some((_, '\\')) => value.push('\\'),
+                Some((_, 'n')) => value.push('\n'),
+                _ => {
+                    return Err(ParseError::new(
+                        lineno,
+                        line,
+                        &rest[i..],
+                        "expected `\\\"`, `\\\\` or `\\n`",
+                    ))
+                }
+            },
+            c => value.push(c),
+        }
+    }
+    Err(ParseError::new(lineno, line, "", "expected a closing `\"`"))
+}
+
+/// Reads a `'''literal'''` which may run over several lines, given what follows
+/// its opening quotes. As in TOML, a newline straight after the opening quotes
+/// isn't part of the value.
+fn read_multi_line<'a>(
+    text: &str,
+    lineno: usize,
+    line: &str,
+    rest: &str,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<String, ParseError> {
+    if let Some((value, after)) = rest.split_once("'''") {
+        if !after.trim().is_empty() {
+            return Err(ParseError::new(
+                lineno,
+                line,
+                after,
+                "unexpected characters after the string",
+            ));
+        }
+        return Ok(value.to_string());
+    }
+
+    let mut value_lines = if rest.is_empty() { vec![] } else { vec![rest] };
+    for (lineno, line) in lines {
+        if let Some((last, after)) = line.split_once("'''") {
+            if !after.trim().is_empty() {
+                return Err(ParseError::new(
+                    lineno,
+                    line,
+                    after,
+                    "unexpected characters after the string",
+                ));
+            }
+            value_lines.push(last);
+            return Ok(value_lines.join("\n"));
+        }
+        value_lines.push(line);
+    }
+    Err(ParseError::unexpected_end(text, "expected a closing `'''`"))
+}
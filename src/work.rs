@@ -0,0 +1,51 @@
+//! Counting the steps solutions take through the shared automaton and search
+//! helpers, so that the fuzz tests can stop one that runs away after a set
+//! amount of work, the same on any machine, rather than after a set time.
+
+#[cfg(test)]
+use std::cell::Cell;
+#[cfg(test)]
+use std::panic::{self, AssertUnwindSafe};
+
+#[cfg(test)]
+thread_local! {
+    /// How many more steps this thread may take, if it's being counted.
+    static BUDGET: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Counts a step. Outside of tests this does nothing.
+#[inline]
+pub(crate) fn step() {
+    #[cfg(test)]
+    BUDGET.with(|budget| match budget.get() {
+        Some(0) => panic::panic_any(OutOfWork),
+        Some(left) => budget.set(Some(left - 1)),
+        None => {}
+    });
+}
+
+/// What a thread panics with when it runs out of steps.
+#[cfg(test)]
+struct OutOfWork;
+
+/// Runs `f` with `steps` steps to spend, giving `None` if it runs out of them.
+/// Any other panic carries on unwinding.
+#[cfg(test)]
+pub(crate) fn limited<T>(steps: usize, f: impl FnOnce() -> T) -> Option<T> {
+    BUDGET.set(Some(steps));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    BUDGET.set(None);
+    match result {
+        Ok(value) => Some(value),
+        Err(payload) if payload.is::<OutOfWork>() => None,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+#[test]
+fn stops_after_the_steps_given() {
+    assert_eq!(Some(()), limited(10, || (0..10).for_each(|_| step())));
+    assert_eq!(None, limited(10, || (0..11).for_each(|_| step())));
+    // Nothing's counted outside of `limited`.
+    (0..11).for_each(|_| step());
+}